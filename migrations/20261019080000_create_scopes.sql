@@ -0,0 +1,6 @@
+create table if not exists scopes
+(
+	name	text primary key not null,
+	color	text,
+	label	text
+)
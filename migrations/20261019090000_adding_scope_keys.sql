@@ -0,0 +1,25 @@
+ALTER TABLE scopes
+ADD key text;
+
+ALTER TABLE scopes
+ADD next_number integer NOT NULL DEFAULT 1;
+
+CREATE UNIQUE INDEX scopes_key ON scopes (key);
+
+ALTER TABLE tasks
+ADD scope_number integer;
+
+UPDATE tasks
+SET scope_number = (
+	SELECT COUNT(*) FROM tasks previous
+	WHERE previous.scope = tasks.scope AND previous.id <= tasks.id
+)
+WHERE scope IS NOT NULL;
+
+INSERT OR IGNORE INTO scopes (name)
+SELECT DISTINCT scope FROM tasks WHERE scope IS NOT NULL;
+
+UPDATE scopes
+SET next_number = COALESCE(
+	(SELECT MAX(scope_number) FROM tasks WHERE tasks.scope = scopes.name), 0
+) + 1;
@@ -0,0 +1,21 @@
+CREATE VIRTUAL TABLE tasks_fts USING fts5(
+	description,
+	content = 'tasks',
+	content_rowid = 'id',
+	prefix = '2 3'
+);
+
+CREATE TRIGGER tasks_fts_insert AFTER INSERT ON tasks BEGIN
+	INSERT INTO tasks_fts (rowid, description) VALUES (new.id, new.description);
+END;
+
+CREATE TRIGGER tasks_fts_delete AFTER DELETE ON tasks BEGIN
+	INSERT INTO tasks_fts (tasks_fts, rowid, description) VALUES ('delete', old.id, old.description);
+END;
+
+CREATE TRIGGER tasks_fts_update AFTER UPDATE OF description ON tasks BEGIN
+	INSERT INTO tasks_fts (tasks_fts, rowid, description) VALUES ('delete', old.id, old.description);
+	INSERT INTO tasks_fts (rowid, description) VALUES (new.id, new.description);
+END;
+
+INSERT INTO tasks_fts (tasks_fts) VALUES ('rebuild');
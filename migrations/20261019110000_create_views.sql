@@ -0,0 +1,5 @@
+create table if not exists views
+(
+	name	text primary key not null,
+	arguments	text not null
+)
@@ -0,0 +1,2 @@
+ALTER TABLE tasks
+ADD due_at datetime;
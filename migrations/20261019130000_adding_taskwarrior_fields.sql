@@ -0,0 +1,7 @@
+ALTER TABLE tasks
+ADD uuid text;
+
+ALTER TABLE tasks
+ADD taskwarrior_fields text;
+
+CREATE UNIQUE INDEX tasks_uuid ON tasks (uuid);
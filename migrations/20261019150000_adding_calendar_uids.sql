@@ -0,0 +1,4 @@
+ALTER TABLE tasks
+ADD calendar_uid text;
+
+CREATE UNIQUE INDEX tasks_calendar_uid ON tasks (calendar_uid);
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    domain::Task,
+    grouping::{GroupedTasks, TaskGroup},
+};
+
+/// Group of the open tasks due before the agenda starts
+pub const OVERDUE_GROUP: &str = "overdue";
+
+/// Date placing a task in the agenda and the calendar
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Default)]
+pub enum AgendaDate {
+    /// Day the task is due, the agenda looking ahead from today
+    #[default]
+    Due,
+    /// Day the task was created, the agenda looking back from today
+    Created,
+    /// Day the task was completed, the agenda looking back from today
+    Completed,
+}
+
+impl AgendaDate {
+    pub fn of(&self, task: &Task) -> Option<NaiveDate> {
+        match self {
+            AgendaDate::Due => task.due_at.map(|d| d.date_naive()),
+            AgendaDate::Created => Some(task.created_at.date_naive()),
+            AgendaDate::Completed => task.completed_at.map(|d| d.date_naive()),
+        }
+    }
+}
+
+/// Number of tasks of a calendar day
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DayCount {
+    pub date: NaiveDate,
+    pub count: usize,
+}
+
+/// Groups tasks by day over `days` days from today, or up to today for past dates.
+///
+/// Days without tasks are left out. Open tasks due before today come first, in the overdue
+/// group.
+pub fn agenda(tasks: Vec<Task>, date: AgendaDate, today: NaiveDate, days: u32) -> GroupedTasks {
+    let span = Days::new(u64::from(days.saturating_sub(1)));
+    let (first, last) = match date {
+        AgendaDate::Due => (today, today + span),
+        AgendaDate::Created | AgendaDate::Completed => (today - span, today),
+    };
+    let mut overdue = vec![];
+    let mut by_day: Vec<(NaiveDate, Vec<Task>)> = vec![];
+    for task in tasks {
+        let Some(day) = date.of(&task) else {
+            continue;
+        };
+        if date == AgendaDate::Due && day < first && task.completed_at.is_none() {
+            overdue.push(task);
+        } else if (first..=last).contains(&day) {
+            match by_day.iter_mut().find(|(d, _)| *d == day) {
+                Some((_, tasks)) => tasks.push(task),
+                None => by_day.push((day, vec![task])),
+            }
+        }
+    }
+    by_day.sort_by_key(|(day, _)| *day);
+    let groups: Vec<TaskGroup> = (!overdue.is_empty())
+        .then(|| (OVERDUE_GROUP.to_string(), overdue))
+        .into_iter()
+        .chain(
+            by_day
+                .into_iter()
+                .map(|(day, tasks)| (day.format("%a %Y-%m-%d").to_string(), tasks)),
+        )
+        .map(|(name, tasks)| TaskGroup {
+            name,
+            count: tasks.len(),
+            tasks,
+        })
+        .collect();
+    GroupedTasks {
+        total: groups.iter().map(|group| group.count).sum(),
+        groups,
+    }
+}
+
+/// Number of tasks of every day of the month starting on `month`
+pub fn count_month(tasks: &[Task], date: AgendaDate, month: NaiveDate) -> Vec<DayCount> {
+    let mut counts: HashMap<NaiveDate, usize> = HashMap::new();
+    for day in tasks.iter().filter_map(|task| date.of(task)) {
+        *counts.entry(day).or_default() += 1;
+    }
+    month_days(month)
+        .map(|date| DayCount {
+            date,
+            count: counts.get(&date).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+/// Days of the month starting on `month`
+fn month_days(month: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    let next_month = month + Months::new(1);
+    month.iter_days().take_while(move |day| *day < next_month)
+}
+
+/// Command line parser for months written as `YYYY-MM`, giving their first day
+pub fn parse_month(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", value.trim()), "%Y-%m-%d")
+        .map_err(|_| format!("invalid month '{}', expected YYYY-MM", value))
+}
+
+/// First day of the month of `day`
+pub fn start_of_month(day: NaiveDate) -> NaiveDate {
+    day.with_day(1).expect("every month has a first day")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, NaiveDate, TimeZone};
+
+    use crate::domain::Task;
+
+    use super::{agenda, count_month, parse_month, AgendaDate};
+
+    fn task(id: u32, due: Option<u32>, done: bool) -> Task {
+        let at = |day| Local.with_ymd_and_hms(2026, 10, day, 9, 0, 0).unwrap();
+        Task::test(id, &format!("Task {}", id))
+            .set_created_at(at(1))
+            .set_completed_at(done.then(|| at(18)))
+            .set_due_at(due.map(at))
+    }
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn agenda_groups_tasks_by_day_after_the_overdue_ones() {
+        let tasks = vec![
+            task(1, Some(21), false),
+            task(2, Some(12), false),
+            task(3, Some(19), false),
+            task(4, Some(12), true),
+            task(5, Some(30), false),
+            task(6, None, false),
+            task(7, Some(21), true),
+        ];
+        let grouped = agenda(tasks, AgendaDate::Due, day(19), 7);
+        let summary: Vec<(String, Vec<String>)> = grouped
+            .groups
+            .iter()
+            .map(|g| {
+                (
+                    g.name.clone(),
+                    g.tasks.iter().map(|t| t.id.to_string()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("overdue".to_string(), vec!["2".to_string()]),
+                ("Mon 2026-10-19".to_string(), vec!["3".to_string()]),
+                (
+                    "Wed 2026-10-21".to_string(),
+                    vec!["1".to_string(), "7".to_string()]
+                ),
+            ]
+        );
+        assert_eq!(grouped.total, 4);
+        let completed = agenda(vec![task(1, None, true)], AgendaDate::Completed, day(19), 2);
+        assert_eq!(completed.groups[0].name, "Sun 2026-10-18");
+    }
+
+    #[test]
+    fn months_are_counted_day_by_day() {
+        let month = parse_month("2026-10").unwrap();
+        let tasks = [task(1, Some(3), false), task(2, Some(3), true)];
+        let counts = count_month(&tasks, AgendaDate::Due, month);
+        assert_eq!(counts.len(), 31);
+        assert_eq!((counts[2].date, counts[2].count), (day(3), 2));
+        assert_eq!(counts[3].count, 0);
+        assert!(parse_month("2026-13").is_err());
+    }
+}
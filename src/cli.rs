@@ -1,10 +1,37 @@
-use clap::{command, Parser, Subcommand};
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand};
+
+use crate::{
+    agenda::{parse_month, AgendaDate},
+    dates::{parse_date_spec, parse_due_date, DateSpec},
+    domain::{parse_key_prefix, Color, Scope, SortKey, TaskRef},
+    export::ExportFormat,
+    grouping::{BoardLanes, GroupBy},
+    import::ImportFormat,
+    output::OutputFormat,
+    tabular::ColumnSpec,
+    theme::ColorMode,
+};
+
+/// Longest agenda, about ten years
+const MAX_AGENDA_DAYS: i64 = 3660;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 pub struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
+    /// Output format
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+    /// When to color tables, overriding the configuration
+    #[arg(long, global = true, value_enum)]
+    color: Option<ColorMode>,
+    /// Print long listings directly instead of through $PAGER
+    #[arg(long, global = true)]
+    no_pager: bool,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -16,23 +43,111 @@ pub enum Commands {
         /// Task scope
         #[arg(long, short)]
         scope: Option<String>,
+        /// Day the task is due: YYYY-MM-DD, today, tomorrow or a delay such as 3d
+        #[arg(long, value_parser = parse_due_date)]
+        due: Option<NaiveDate>,
     },
     /// List tasks
     #[clap(visible_alias = "ls")]
-    List {
+    List(ListArgs),
+    /// Opens a full-screen interface to browse and update tasks
+    Tui,
+    /// Reads commands line by line, keeping the database open between them
+    Shell,
+    /// Shows tasks as cards in side by side lanes
+    Board {
+        /// Scope filter
+        #[arg(long, short)]
+        scope: Option<String>,
+        /// Property giving the lanes of the board
+        #[arg(long, value_enum, value_name = "PROPERTY", default_value_t = BoardLanes::Status)]
+        by: BoardLanes,
+    },
+    /// Lists tasks day by day, by the day they are due, were created or were completed
+    Agenda {
+        /// Number of days listed, from today for due dates and up to today otherwise
+        #[arg(long, default_value_t = 7, value_parser = clap::value_parser!(u32).range(1..=MAX_AGENDA_DAYS))]
+        days: u32,
+        /// Date placing tasks in the agenda
+        #[arg(long, value_enum, value_name = "DATE", default_value_t = AgendaDate::Due)]
+        by: AgendaDate,
+        /// Scope filter
+        #[arg(long, short)]
+        scope: Option<String>,
+    },
+    /// Draws a month with the number of tasks of each day
+    Calendar {
+        /// Month drawn, as YYYY-MM; the current one by default
+        #[arg(long, value_parser = parse_month)]
+        month: Option<NaiveDate>,
+        /// Date placing tasks in the calendar
+        #[arg(long, value_enum, value_name = "DATE", default_value_t = AgendaDate::Due)]
+        by: AgendaDate,
+        /// Scope filter
+        #[arg(long, short)]
+        scope: Option<String>,
+    },
+    /// Writes tasks as a document, such as a todo.txt file, a Taskwarrior export or a calendar
+    Export {
+        /// Document format
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+        /// File written, stdout when omitted
+        file: Option<PathBuf>,
         /// Scope filter
         #[arg(long, short)]
         scope: Option<String>,
+        /// Filter expression, e.g. 'done and completed<7d'
+        #[arg(long, short)]
+        filter: Option<String>,
+    },
+    /// Adds the tasks of a todo.txt, CSV or JSON file
+    Import {
+        /// File read, - for stdin
+        file: PathBuf,
+        /// Format of the file
+        #[arg(long, value_enum)]
+        format: ImportFormat,
+        /// Lists the tasks that would be added without saving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Full-text search over task descriptions
+    Search {
+        /// Words to look for; supports "exact phrases", prefix* and AND/OR/NOT
+        query: String,
+        /// Scope filter
+        #[arg(long, short)]
+        scope: Option<String>,
+    },
+    /// Shows task details
+    Show {
+        /// Task id or scope key, e.g. 12 or WEB-12
+        #[arg(value_name = "TASK_ID")]
+        id: TaskRef,
+        /// Output template, e.g. '{ref}\t{?scope}{scope}{/scope}\t{created_at:%Y-%m-%d}'
+        #[arg(long, value_name = "TEMPLATE")]
+        format: Option<String>,
     },
     /// Toggles task completion
     Complete {
+        /// Task id or scope key, e.g. 12 or WEB-12
         #[arg(value_name = "TASK_ID")]
-        id: u32,
+        id: TaskRef,
     },
     /// Deletes task
     Delete {
+        /// Task id or scope key, e.g. 12 or WEB-12
         #[arg(value_name = "TASK_ID")]
-        id: u32,
+        id: TaskRef,
+    },
+    /// Lists tasks using a saved view, or manages views
+    #[command(args_conflicts_with_subcommands = true)]
+    View {
+        /// Name of the view to list tasks with
+        name: Option<String>,
+        #[command(subcommand)]
+        action: Option<ViewCommands>,
     },
     /// Scope management actions
     Scope {
@@ -41,15 +156,255 @@ pub enum Commands {
     },
 }
 
+#[derive(Args, Debug, Clone, Default)]
+pub struct ListArgs {
+    /// Saved view to start from, e.g. @standup; other options override the view ones
+    #[arg(value_name = "@VIEW", value_parser = parse_view_reference)]
+    pub view: Option<String>,
+    /// Scope filter
+    #[arg(long, short)]
+    pub scope: Option<String>,
+    /// Filter expression, e.g. 'scope:work and not done and (created<7d or desc~"deploy")'
+    #[arg(long, short)]
+    pub filter: Option<String>,
+    /// Only list tasks that are not completed
+    #[arg(long, conflicts_with = "done")]
+    pub open: bool,
+    /// Only list completed tasks
+    #[arg(long)]
+    pub done: bool,
+    /// Only list tasks created on or after a date (YYYY-MM-DD, today, yesterday) or age (7d)
+    #[arg(long, value_name = "DATE", value_parser = parse_date_spec)]
+    pub created_since: Option<DateSpec>,
+    /// Only list tasks created before a date or age
+    #[arg(long, value_name = "DATE", value_parser = parse_date_spec)]
+    pub created_before: Option<DateSpec>,
+    /// Only list tasks completed on or after a date or age
+    #[arg(long, value_name = "DATE", value_parser = parse_date_spec)]
+    pub completed_since: Option<DateSpec>,
+    /// Only list tasks completed before a date or age
+    #[arg(long, value_name = "DATE", value_parser = parse_date_spec)]
+    pub completed_before: Option<DateSpec>,
+    /// Sort keys among id, description, scope, created and completed; prefix with - to sort descending
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "KEYS",
+        allow_hyphen_values = true
+    )]
+    pub sort: Vec<SortKey>,
+    /// Reverse the sort order
+    #[arg(long, short, overrides_with = "no_reverse")]
+    pub reverse: bool,
+    /// Keep the sort order of a view that reverses it
+    #[arg(long, overrides_with = "reverse")]
+    pub no_reverse: bool,
+    /// Maximum number of tasks to list
+    #[arg(long, short)]
+    pub limit: Option<u32>,
+    /// Number of tasks to skip
+    #[arg(long)]
+    pub offset: Option<u32>,
+    /// Table columns among id, description, scope, created, created_relative, age, done,
+    /// completed, status and due, with optional weights sharing the free space, e.g.
+    /// id,scope,description:8,age
+    #[arg(long, value_delimiter = ',', value_name = "COLUMNS")]
+    pub columns: Vec<ColumnSpec>,
+    /// Color whole rows with their scope color instead of only the scope column
+    #[arg(long, overrides_with = "no_color_rows")]
+    pub color_rows: bool,
+    /// Only color the scope column, even when the view colors whole rows
+    #[arg(long, overrides_with = "color_rows")]
+    pub no_color_rows: bool,
+    /// Wrap long descriptions over several lines instead of truncating them
+    #[arg(long, overrides_with = "no_wrap")]
+    pub wrap: bool,
+    /// Truncate long descriptions, even when the view wraps them
+    #[arg(long, overrides_with = "wrap")]
+    pub no_wrap: bool,
+    /// Split the listing in sections, with a count of tasks per section
+    #[arg(long, value_enum, value_name = "PROPERTY")]
+    pub group_by: Option<GroupBy>,
+    /// Output template instead of a table, e.g. '{id:>4} {?scope}[{scope}] {/scope}{description}'
+    #[arg(long, value_name = "TEMPLATE")]
+    pub format: Option<String>,
+}
+
+impl Commands {
+    /// Gives `scope` to the commands taking a scope when none was given
+    pub fn with_default_scope(self, scope: &Scope) -> Self {
+        let default = |value: Option<String>| value.or_else(|| Some(scope.to_string()));
+        match self {
+            Commands::Add {
+                description,
+                scope,
+                due,
+            } => Commands::Add {
+                description,
+                scope: default(scope),
+                due,
+            },
+            Commands::List(args) if args.view.is_none() => Commands::List(ListArgs {
+                scope: default(args.scope),
+                ..args
+            }),
+            Commands::Board { scope, by } => Commands::Board {
+                scope: default(scope),
+                by,
+            },
+            Commands::Agenda { days, by, scope } => Commands::Agenda {
+                days,
+                by,
+                scope: default(scope),
+            },
+            Commands::Calendar { month, by, scope } => Commands::Calendar {
+                month,
+                by,
+                scope: default(scope),
+            },
+            Commands::Export {
+                format,
+                file,
+                scope,
+                filter,
+            } => Commands::Export {
+                format,
+                file,
+                scope: default(scope),
+                filter,
+            },
+            Commands::Search { query, scope } => Commands::Search {
+                query,
+                scope: default(scope),
+            },
+            command => command,
+        }
+    }
+}
+
+impl ListArgs {
+    /// Parses the list arguments stored in a view
+    pub fn from_view_arguments(arguments: &[String]) -> Result<Self, clap::Error> {
+        #[derive(Parser)]
+        #[command(name = "view", no_binary_name = true)]
+        struct ViewArguments {
+            #[command(flatten)]
+            list: ListArgs,
+        }
+        ViewArguments::try_parse_from(arguments).map(|a| a.list)
+    }
+
+    /// Completes the options not given explicitly with the ones of a view
+    pub fn merge(self, view: ListArgs) -> ListArgs {
+        let (open, done) = if self.open || self.done {
+            (self.open, self.done)
+        } else {
+            (view.open, view.done)
+        };
+        ListArgs {
+            view: None,
+            scope: self.scope.or(view.scope),
+            filter: self.filter.or(view.filter),
+            open,
+            done,
+            created_since: self.created_since.or(view.created_since),
+            created_before: self.created_before.or(view.created_before),
+            completed_since: self.completed_since.or(view.completed_since),
+            completed_before: self.completed_before.or(view.completed_before),
+            sort: if self.sort.is_empty() {
+                view.sort
+            } else {
+                self.sort
+            },
+            reverse: self.reverse || (view.reverse && !self.no_reverse),
+            no_reverse: false,
+            limit: self.limit.or(view.limit),
+            offset: self.offset.or(view.offset),
+            columns: if self.columns.is_empty() {
+                view.columns
+            } else {
+                self.columns
+            },
+            color_rows: self.color_rows || (view.color_rows && !self.no_color_rows),
+            no_color_rows: false,
+            wrap: self.wrap || (view.wrap && !self.no_wrap),
+            no_wrap: false,
+            group_by: self.group_by.or(view.group_by),
+            format: self.format.or(view.format),
+        }
+    }
+}
+
+fn parse_view_reference(value: &str) -> Result<String, String> {
+    value
+        .strip_prefix('@')
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            format!(
+                "expected a view reference such as @standup, got '{}'",
+                value
+            )
+        })
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ViewCommands {
+    /// Saves list options under a name, e.g. `view save standup --scope work --done`
+    Save {
+        name: String,
+        /// Options of the list command
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        arguments: Vec<String>,
+    },
+    /// Replaces the list options of a view
+    Edit {
+        name: String,
+        /// Options of the list command
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        arguments: Vec<String>,
+    },
+    /// Lists saved views
+    List,
+    /// Deletes a view
+    Delete { name: String },
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum ScopeCommands {
+    /// List scopes with their display metadata
     List,
+    /// Sets the color used to display a scope, clearing it when omitted
+    SetColor { scope: String, color: Option<Color> },
+    /// Sets a short label or emoji shown next to a scope, clearing it when omitted
+    SetLabel {
+        scope: String,
+        label: Option<String>,
+    },
+    /// Sets the key prefix used to number scope tasks, e.g. WEB for WEB-12
+    SetKey {
+        scope: String,
+        #[arg(value_parser = parse_key_prefix)]
+        key: Option<String>,
+    },
 }
 
 impl Cli {
     pub fn get_command(&self) -> Commands {
         self.command
             .clone()
-            .unwrap_or(Commands::List { scope: None })
+            .unwrap_or(Commands::List(ListArgs::default()))
+    }
+
+    pub fn get_output(&self) -> OutputFormat {
+        self.output
+    }
+
+    pub fn get_color(&self) -> Option<ColorMode> {
+        self.color
+    }
+
+    pub fn get_no_pager(&self) -> bool {
+        self.no_pager
     }
 }
@@ -1,12 +1,80 @@
-use std::path::PathBuf;
+use std::{fs, path::Path, path::PathBuf};
 
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::{
+    tabular::ColumnSpec,
+    theme::{ColorMode, Theme},
+};
+
+#[derive(Clone)]
 pub struct Settings {
     pub location: PathBuf,
+    pub list: ListSettings,
+    pub color: ColorMode,
+    pub theme: Theme,
+    /// Whether listings taller than the terminal go through $PAGER
+    pub pager: bool,
 }
 impl Settings {
     pub fn new(location: PathBuf) -> Self {
-        Self { location }
+        Self {
+            location,
+            list: ListSettings::default(),
+            color: ColorMode::default(),
+            theme: Theme::default(),
+            pager: true,
+        }
     }
+
+    /// Reads user preferences from a TOML file, keeping defaults when it does not exist
+    pub fn with_config_file(mut self, path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(self);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read configuration at {}", path.display()))?;
+        let config: ConfigFile = toml::from_str(&content)
+            .with_context(|| format!("Invalid configuration at {}", path.display()))?;
+        self.list = config.list;
+        self.color = config.color;
+        self.theme = config.theme;
+        if let Some(pager) = config.pager {
+            self.pager = pager;
+        }
+        Ok(self)
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    list: ListSettings,
+    /// When to color tables: `always`, `auto` or `never`
+    color: ColorMode,
+    theme: Theme,
+    /// Set to false to never page listings
+    pager: Option<bool>,
+}
+
+/// Defaults of the `list` command, from the `[list]` section of the configuration
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ListSettings {
+    /// Table columns, e.g. `"id,scope,description:8,age"`
+    #[serde(deserialize_with = "deserialize_columns")]
+    pub columns: Vec<ColumnSpec>,
+}
+
+fn deserialize_columns<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<ColumnSpec>, D::Error> {
+    let columns = String::deserialize(deserializer)?;
+    columns
+        .split(',')
+        .map(|column| column.parse().map_err(serde::de::Error::custom))
+        .collect()
 }
 
 pub struct DatabaseSettings {
@@ -0,0 +1,201 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+
+/// Point in time given by the user, either relative to now or a calendar day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateSpec {
+    Ago(Duration),
+    Day(NaiveDate),
+}
+
+impl DateSpec {
+    /// Parses `7d`, `12h`, `2w`, `today`, `yesterday`, `tomorrow` or `YYYY-MM-DD`
+    pub fn parse(value: &str, today: NaiveDate) -> Option<Self> {
+        parse_duration(value)
+            .map(Self::Ago)
+            .or_else(|| parse_day(value, today).map(Self::Day))
+    }
+
+    /// Earliest moment the specification refers to, `None` when it is out of range
+    pub fn resolve(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            DateSpec::Ago(duration) => now.checked_sub_signed(*duration),
+            DateSpec::Day(day) => Some(start_of_day(*day)),
+        }
+    }
+}
+
+/// Command line parser for date specifications relative to today
+pub fn parse_date_spec(value: &str) -> Result<DateSpec, String> {
+    let now = Local::now();
+    let spec = DateSpec::parse(value, now.date_naive()).ok_or_else(|| {
+        format!(
+            "invalid date '{}', expected YYYY-MM-DD, today, yesterday or an age such as 12h, 7d, 2w",
+            value
+        )
+    })?;
+    match spec.resolve(now) {
+        Some(_) => Ok(spec),
+        None => Err(format!("date '{}' is too far in the past", value)),
+    }
+}
+
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim().to_lowercase();
+    let unit_start = value.find(|c: char| !c.is_ascii_digit())?;
+    let amount: i64 = value[..unit_start].parse().ok()?;
+    match &value[unit_start..] {
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+pub fn parse_day(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match value.trim().to_lowercase().as_str() {
+        "today" => Some(today),
+        "yesterday" => today.pred_opt(),
+        "tomorrow" => today.succ_opt(),
+        other => NaiveDate::parse_from_str(other, "%Y-%m-%d").ok(),
+    }
+}
+
+/// Command line parser for due dates: a day, or a duration from today such as `3d` or `2w`
+pub fn parse_due_date(value: &str) -> Result<NaiveDate, String> {
+    let today = Local::now().date_naive();
+    parse_day(value, today)
+        .or_else(|| {
+            parse_duration(value).and_then(|duration| today.checked_add_signed(duration))
+        })
+        .ok_or_else(|| {
+            format!(
+                "invalid due date '{}', expected YYYY-MM-DD, today, tomorrow or a delay such as 3d, 2w",
+                value
+            )
+        })
+}
+
+/// Compact age such as `45m`, `3h`, `2d`, `3w`, `4mo` or `1y`
+pub fn format_age(age: Duration) -> String {
+    let (amount, unit) = largest_unit(age);
+    let unit = match unit {
+        "minute" => "m",
+        "hour" => "h",
+        "day" => "d",
+        "week" => "w",
+        "month" => "mo",
+        _ => "y",
+    };
+    format!("{}{}", amount, unit)
+}
+
+/// Relative time such as `just now`, `5 minutes ago` or `2 days ago`
+pub fn format_relative(age: Duration) -> String {
+    if age < Duration::minutes(1) {
+        return "just now".to_string();
+    }
+    let (amount, unit) = largest_unit(age);
+    let plural = if amount == 1 { "" } else { "s" };
+    format!("{} {}{} ago", amount, unit, plural)
+}
+
+fn largest_unit(age: Duration) -> (i64, &'static str) {
+    let minutes = age.num_minutes().max(0);
+    let days = age.num_days().max(0);
+    match (minutes, days) {
+        (m, _) if m < 60 => (m, "minute"),
+        (m, 0) => (m / 60, "hour"),
+        (_, d) if d < 14 => (d, "day"),
+        (_, d) if d < 60 => (d / 7, "week"),
+        (_, d) if d < 365 => (d / 30, "month"),
+        (_, d) => (d / 365, "year"),
+    }
+}
+
+/// Parses an RFC 3339 timestamp, a local `YYYY-MM-DD HH:MM:SS` time or a day, read as its start
+pub fn parse_datetime(value: &str) -> Option<DateTime<Local>> {
+    let value = value.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.with_timezone(&Local));
+    }
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .and_then(|datetime| Local.from_local_datetime(&datetime).earliest())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(start_of_day)
+        })
+}
+
+pub fn start_of_day(day: NaiveDate) -> DateTime<Local> {
+    let midnight = day.and_hms_opt(0, 0, 0).unwrap();
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&midnight))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Local, NaiveDate};
+
+    use super::{format_age, format_relative, parse_date_spec, parse_due_date, DateSpec};
+
+    #[test]
+    fn ages_and_days_are_parsed() {
+        let today = NaiveDate::from_ymd_opt(2024, 8, 10).unwrap();
+        let cases = [
+            ("12h", Some(DateSpec::Ago(Duration::hours(12)))),
+            ("7D", Some(DateSpec::Ago(Duration::days(7)))),
+            ("2w", Some(DateSpec::Ago(Duration::weeks(2)))),
+            ("today", Some(DateSpec::Day(today))),
+            ("yesterday", today.pred_opt().map(DateSpec::Day)),
+            (
+                "2024-08-01",
+                NaiveDate::from_ymd_opt(2024, 8, 1).map(DateSpec::Day),
+            ),
+            ("7", None),
+            ("d", None),
+            ("soon", None),
+            ("999999999999999d", None),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(expected, DateSpec::parse(value, today), "{}", value);
+        }
+    }
+
+    #[test]
+    fn ages_beyond_the_calendar_do_not_resolve() {
+        let now = Local::now();
+        let age = DateSpec::Ago(Duration::days(100_000_000));
+        assert_eq!(None, age.resolve(now));
+        let age = DateSpec::Ago(Duration::days(7));
+        assert_eq!(Some(now - Duration::days(7)), age.resolve(now));
+        assert!(parse_date_spec("100000000d").is_err());
+    }
+
+    #[test]
+    fn due_dates_beyond_the_calendar_are_rejected() {
+        assert!(parse_due_date("3d").is_ok());
+        assert!(parse_due_date("100000000d").is_err());
+    }
+
+    #[test]
+    fn ages_are_formatted_with_largest_unit() {
+        let cases = [
+            (Duration::seconds(20), "0m", "just now"),
+            (Duration::minutes(1), "1m", "1 minute ago"),
+            (Duration::minutes(150), "2h", "2 hours ago"),
+            (Duration::days(3), "3d", "3 days ago"),
+            (Duration::days(21), "3w", "3 weeks ago"),
+            (Duration::days(90), "3mo", "3 months ago"),
+            (Duration::days(800), "2y", "2 years ago"),
+        ];
+        for (age, compact, relative) in cases {
+            assert_eq!(compact, format_age(age));
+            assert_eq!(relative, format_relative(age));
+        }
+    }
+}
@@ -1,9 +1,23 @@
+mod color;
 mod new_task;
 mod scope;
+mod scope_details;
+mod sort_key;
 mod task;
 mod task_id;
+mod task_key;
+mod task_ref;
+mod task_status;
+mod view;
 
+pub use color::Color;
 pub use new_task::NewTask;
 pub use scope::Scope;
+pub use scope_details::ScopeDetails;
+pub use sort_key::{SortField, SortKey};
 pub use task::Task;
 pub use task_id::TaskId;
+pub use task_key::{parse_key_prefix, TaskKey};
+pub use task_ref::TaskRef;
+pub use task_status::TaskStatus;
+pub use view::View;
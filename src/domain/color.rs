@@ -0,0 +1,90 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+const COLORS: [(Color, &str); 16] = [
+    (Color::Black, "black"),
+    (Color::Red, "red"),
+    (Color::Green, "green"),
+    (Color::Yellow, "yellow"),
+    (Color::Blue, "blue"),
+    (Color::Magenta, "magenta"),
+    (Color::Cyan, "cyan"),
+    (Color::White, "white"),
+    (Color::BrightBlack, "bright-black"),
+    (Color::BrightRed, "bright-red"),
+    (Color::BrightGreen, "bright-green"),
+    (Color::BrightYellow, "bright-yellow"),
+    (Color::BrightBlue, "bright-blue"),
+    (Color::BrightMagenta, "bright-magenta"),
+    (Color::BrightCyan, "bright-cyan"),
+    (Color::BrightWhite, "bright-white"),
+];
+
+impl Color {
+    /// ANSI SGR code setting this color as the foreground
+    pub fn ansi_code(&self) -> u8 {
+        let index = COLORS.iter().position(|(c, _)| c == self).unwrap() as u8;
+        if index < 8 {
+            30 + index
+        } else {
+            90 + index - 8
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        COLORS.iter().find(|(c, _)| c == self).unwrap().1
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        COLORS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(c, _)| *c)
+            .ok_or_else(|| {
+                let names: Vec<&str> = COLORS.iter().map(|(_, n)| *n).collect();
+                format!(
+                    "unknown color '{}', expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
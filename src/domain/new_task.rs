@@ -1,7 +1,21 @@
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
 use super::Scope;
 
-#[derive(Debug)]
+#[derive(Debug, Default, Serialize)]
 pub struct NewTask {
     pub description: String,
     pub scope: Option<Scope>,
+    pub due_at: Option<DateTime<Local>>,
+    /// Creation time kept by imported tasks, now when missing
+    pub created_at: Option<DateTime<Local>>,
+    pub completed_at: Option<DateTime<Local>>,
+    /// Taskwarrior identity of the task, updating it when imported again
+    pub uuid: Option<String>,
+    /// JSON object of the Taskwarrior fields without an equivalent, such as tags; `None` when
+    /// the import does not come from Taskwarrior, keeping the fields saved before
+    pub taskwarrior_fields: Option<String>,
+    /// UID of a VTODO that is not a uuid, such as `abc@google.com`
+    pub calendar_uid: Option<String>,
 }
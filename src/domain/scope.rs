@@ -1,7 +1,10 @@
 use std::fmt::Display;
 
-#[derive(Debug, sqlx::Type)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, sqlx::Type, Serialize)]
 #[sqlx(transparent)]
+#[serde(transparent)]
 pub struct Scope(String);
 
 impl Scope {
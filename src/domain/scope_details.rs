@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+use super::{Color, Scope};
+
+/// Display metadata attached to a scope
+#[derive(Debug, Clone, Serialize)]
+pub struct ScopeDetails {
+    #[serde(rename = "name")]
+    pub scope: Scope,
+    pub color: Option<Color>,
+    pub label: Option<String>,
+    pub key: Option<String>,
+}
+
+impl ScopeDetails {
+    /// Scope name prefixed by its label, if any
+    pub fn display_name(&self) -> String {
+        match self.label.as_ref() {
+            Some(label) => format!("{} {}", label, self.scope),
+            None => self.scope.to_string(),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortField {
+    Id,
+    Description,
+    Scope,
+    Created,
+    Completed,
+}
+
+const SORT_FIELDS: [(SortField, &str); 5] = [
+    (SortField::Id, "id"),
+    (SortField::Description, "description"),
+    (SortField::Scope, "scope"),
+    (SortField::Created, "created"),
+    (SortField::Completed, "completed"),
+];
+
+/// Sorting criterion such as `scope` or `-created`, a leading `-` meaning descending
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+impl SortKey {
+    pub fn new(field: SortField, descending: bool) -> Self {
+        Self { field, descending }
+    }
+
+    pub fn reversed(self) -> Self {
+        Self::new(self.field, !self.descending)
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (name, descending) = match s.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (s.strip_prefix('+').unwrap_or(s), false),
+        };
+        let name = match name.to_lowercase().as_str() {
+            "desc" => "description".to_string(),
+            other => other.to_string(),
+        };
+        SORT_FIELDS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(field, _)| Self::new(*field, descending))
+            .ok_or_else(|| {
+                let names: Vec<&str> = SORT_FIELDS.iter().map(|(_, n)| *n).collect();
+                format!(
+                    "unknown sort field '{}', expected one of: {}",
+                    name,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = SORT_FIELDS
+            .iter()
+            .find(|(c, _)| *c == self.field)
+            .unwrap()
+            .1;
+        let sign = if self.descending { "-" } else { "" };
+        write!(f, "{}{}", sign, name)
+    }
+}
@@ -1,18 +1,21 @@
 use ::chrono::{NaiveDateTime, TimeZone};
 use chrono::{DateTime, Local};
+use serde::Serialize;
 use sqlx::types::chrono;
 
 use crate::generator::Generator;
 
-use super::{NewTask, Scope, TaskId};
+use super::{NewTask, Scope, TaskId, TaskKey, TaskStatus};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Task {
     pub id: TaskId,
+    pub key: Option<TaskKey>,
     pub description: String,
     pub completed_at: Option<DateTime<Local>>,
     pub created_at: DateTime<Local>,
     pub scope: Option<Scope>,
+    pub due_at: Option<DateTime<Local>>,
 }
 
 impl Task {
@@ -20,9 +23,11 @@ impl Task {
         Self {
             id: TaskId::new(generator),
             description: input.description,
-            completed_at: None,
-            created_at: Local::now(),
+            completed_at: input.completed_at,
+            created_at: input.created_at.unwrap_or_else(Local::now),
             scope: input.scope,
+            key: None,
+            due_at: input.due_at,
         }
     }
 
@@ -32,18 +37,46 @@ impl Task {
         completed_at: Option<NaiveDateTime>,
         created_at: NaiveDateTime,
         scope: Option<String>,
+        key: Option<TaskKey>,
+        due_at: Option<NaiveDateTime>,
     ) -> Option<Self> {
         let created_at = try_parse_datetime(created_at)?;
         let completed_at = completed_at.and_then(try_parse_datetime);
+        let due_at = due_at.and_then(try_parse_datetime);
         Some(Self {
             id: TaskId::from(id),
             description,
             completed_at,
             created_at,
             scope: scope.map(Scope::new),
+            key,
+            due_at,
         })
     }
 
+    /// Scope key when the task has one, global id otherwise
+    pub fn display_id(&self) -> String {
+        match self.key.as_ref() {
+            Some(key) => key.to_string(),
+            None => self.id.to_string(),
+        }
+    }
+
+    pub fn status(&self) -> TaskStatus {
+        match self.completed_at {
+            Some(_) => TaskStatus::Done,
+            None => TaskStatus::Open,
+        }
+    }
+
+    /// Whether the task is still open after the day it was due
+    pub fn is_overdue(&self, now: DateTime<Local>) -> bool {
+        self.completed_at.is_none()
+            && self
+                .due_at
+                .is_some_and(|due_at| due_at.date_naive() < now.date_naive())
+    }
+
     pub fn toggle_complete(&mut self) {
         self.completed_at = self
             .completed_at
@@ -57,3 +90,39 @@ fn try_parse_datetime(date: NaiveDateTime) -> Option<DateTime<Local>> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+impl Task {
+    /// Open task without scope, created on 2024-08-05 at 10:30, to build test fixtures from
+    pub fn test(id: u32, description: &str) -> Self {
+        Self {
+            id: TaskId::from(id),
+            key: None,
+            description: description.to_string(),
+            completed_at: None,
+            created_at: Local.with_ymd_and_hms(2024, 8, 5, 10, 30, 0).unwrap(),
+            scope: None,
+            due_at: None,
+        }
+    }
+
+    pub fn set_scope(mut self, scope: Option<&str>) -> Self {
+        self.scope = scope.map(|s| Scope::new(s.to_string()));
+        self
+    }
+
+    pub fn set_created_at(mut self, created_at: DateTime<Local>) -> Self {
+        self.created_at = created_at;
+        self
+    }
+
+    pub fn set_completed_at(mut self, completed_at: Option<DateTime<Local>>) -> Self {
+        self.completed_at = completed_at;
+        self
+    }
+
+    pub fn set_due_at(mut self, due_at: Option<DateTime<Local>>) -> Self {
+        self.due_at = due_at;
+        self
+    }
+}
@@ -1,9 +1,12 @@
 use std::fmt::Display;
 
+use serde::Serialize;
+
 use crate::generator::Generator;
 
-#[derive(Debug, PartialEq, Clone, sqlx::Type)]
+#[derive(Debug, PartialEq, Clone, sqlx::Type, Serialize)]
 #[sqlx(transparent)]
+#[serde(transparent)]
 pub struct TaskId(u32);
 
 impl TaskId {
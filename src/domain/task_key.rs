@@ -0,0 +1,68 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::Serialize;
+
+const MAX_PREFIX_LEN: usize = 10;
+
+/// Scope-local task reference such as `WEB-12`
+#[derive(Debug, PartialEq, Clone)]
+pub struct TaskKey {
+    pub prefix: String,
+    pub number: u32,
+}
+
+impl TaskKey {
+    pub fn new(prefix: String, number: u32) -> Self {
+        Self { prefix, number }
+    }
+
+    /// Builds a key when the task scope has a prefix and the task a scope number
+    pub fn from_parts(prefix: Option<String>, number: Option<u32>) -> Option<Self> {
+        Some(Self::new(prefix?, number?))
+    }
+}
+
+/// Validates a scope key prefix, returning it uppercased
+pub fn parse_key_prefix(value: &str) -> Result<String, String> {
+    let prefix = value.trim().to_uppercase();
+    let mut chars = prefix.chars();
+    let starts_with_letter = chars.next().is_some_and(|c| c.is_ascii_alphabetic());
+    if !starts_with_letter
+        || !chars.all(|c| c.is_ascii_alphanumeric())
+        || prefix.len() > MAX_PREFIX_LEN
+    {
+        return Err(format!(
+            "invalid key '{}', expected a letter followed by up to {} letters or digits",
+            value,
+            MAX_PREFIX_LEN - 1
+        ));
+    }
+    Ok(prefix)
+}
+
+impl FromStr for TaskKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, number) = s
+            .rsplit_once('-')
+            .ok_or_else(|| format!("invalid task key '{}', expected e.g. WEB-12", s))?;
+        let prefix = parse_key_prefix(prefix)?;
+        let number = number
+            .parse()
+            .map_err(|_| format!("invalid task number in '{}'", s))?;
+        Ok(Self { prefix, number })
+    }
+}
+
+impl Serialize for TaskKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Display for TaskKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.prefix, self.number)
+    }
+}
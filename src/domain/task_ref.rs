@@ -0,0 +1,57 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::TaskKey;
+
+/// Task reference given by the user, either a global id or a scope key
+#[derive(Debug, PartialEq, Clone)]
+pub enum TaskRef {
+    Id(u32),
+    Key(TaskKey),
+}
+
+impl FromStr for TaskRef {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(id) = s.parse() {
+            return Ok(Self::Id(id));
+        }
+        s.parse().map(Self::Key)
+    }
+}
+
+impl Display for TaskRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskRef::Id(id) => write!(f, "{}", id),
+            TaskRef::Key(key) => write!(f, "{}", key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::TaskKey;
+
+    use super::TaskRef;
+
+    #[test]
+    fn numbers_are_parsed_as_ids() {
+        assert_eq!(Ok(TaskRef::Id(12)), "12".parse());
+    }
+
+    #[test]
+    fn keys_are_parsed_case_insensitively() {
+        let expected = TaskRef::Key(TaskKey::new("WEB".to_string(), 12));
+        assert_eq!(Ok(expected.clone()), "WEB-12".parse());
+        assert_eq!(Ok(expected), "web-12".parse());
+    }
+
+    #[test]
+    fn invalid_references_are_rejected() {
+        assert!("WEB".parse::<TaskRef>().is_err());
+        assert!("WEB-".parse::<TaskRef>().is_err());
+        assert!("1WEB-2".parse::<TaskRef>().is_err());
+        assert!("-3".parse::<TaskRef>().is_err());
+    }
+}
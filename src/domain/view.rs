@@ -0,0 +1,66 @@
+use serde::{Serialize, Serializer};
+
+/// Named set of `list` arguments
+#[derive(Debug, Clone, Serialize)]
+pub struct View {
+    pub name: String,
+    /// Serialized as a command line, which fits in a CSV cell
+    #[serde(serialize_with = "serialize_command_line")]
+    pub arguments: Vec<String>,
+}
+
+impl View {
+    pub fn new(name: String, arguments: Vec<String>) -> Self {
+        Self { name, arguments }
+    }
+
+    /// Arguments as they would be typed in a shell
+    pub fn command_line(&self) -> String {
+        command_line(&self.arguments)
+    }
+}
+
+fn command_line(arguments: &[String]) -> String {
+    arguments
+        .iter()
+        .map(|argument| {
+            if argument.is_empty()
+                || argument.contains(|c: char| c.is_whitespace() || "'\"\\$".contains(c))
+            {
+                format!("'{}'", argument.replace('\'', r"'\''"))
+            } else {
+                argument.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn serialize_command_line<S: Serializer>(
+    arguments: &[String],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&command_line(arguments))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{write_records, OutputFormat};
+
+    use super::View;
+
+    #[test]
+    fn views_are_written_as_command_lines() {
+        let arguments = ["--scope", "work", "--filter", "desc~\"api\""];
+        let view = View::new(
+            "standup".to_string(),
+            arguments.map(str::to_string).to_vec(),
+        );
+        let mut buffer = vec![];
+        write_records(&mut buffer, OutputFormat::Csv, &[view]).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "name,arguments\nstandup,\"--scope work --filter 'desc~\"\"api\"\"'\"\n"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use chrono::{DateTime, Local};
+use clap::ValueEnum;
+
+use crate::{
+    domain::Task,
+    grouping::{group_tasks, GroupBy, NO_GROUP},
+    icalendar::write_icalendar,
+    taskwarrior::{write_taskwarrior, TaskwarriorData},
+};
+
+/// Document formats tasks can be exported to
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    /// One todo.txt line per task, scopes becoming +projects
+    Todotxt,
+    /// Checklists grouped by scope
+    Markdown,
+    /// Self-contained HTML report with one table per scope
+    Html,
+    /// JSON array read by `task import`
+    Taskwarrior,
+    /// iCalendar file of VTODO components
+    Ics,
+}
+
+/// Writes tasks as a document, `now` dating the document and spotting overdue tasks.
+///
+/// `data` holds the Taskwarrior uuids and fields saved by imports, by task id.
+pub fn write_export<W: Write>(
+    writer: &mut W,
+    format: ExportFormat,
+    tasks: Vec<Task>,
+    data: &HashMap<u32, TaskwarriorData>,
+    now: DateTime<Local>,
+) -> io::Result<()> {
+    match format {
+        ExportFormat::Todotxt => {
+            for task in &tasks {
+                writeln!(writer, "{}", todotxt_line(task))?;
+            }
+            Ok(())
+        }
+        ExportFormat::Markdown => write_markdown(writer, tasks),
+        ExportFormat::Html => write_html(writer, tasks, now),
+        ExportFormat::Taskwarrior => write_taskwarrior(writer, &tasks, data),
+        ExportFormat::Ics => write_icalendar(writer, &tasks, data, now),
+    }
+}
+
+/// Line such as `x 2026-10-19 2026-10-12 Deploy the API +ops due:2026-10-20`
+pub fn todotxt_line(task: &Task) -> String {
+    let mut words = vec![];
+    if let Some(completed_at) = task.completed_at {
+        words.push(format!("x {}", completed_at.format("%Y-%m-%d")));
+    }
+    words.push(task.created_at.format("%Y-%m-%d").to_string());
+    words.push(single_line(&task.description));
+    if let Some(scope) = task.scope.as_ref() {
+        let project: Vec<&str> = scope.as_ref().split_whitespace().collect();
+        words.push(format!("+{}", project.join("-")));
+    }
+    if let Some(due_at) = task.due_at {
+        words.push(format!("due:{}", due_at.format("%Y-%m-%d")));
+    }
+    words.join(" ")
+}
+
+/// Joins the lines of a description, as line breaks would start another task
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn scope_heading(name: &str) -> &str {
+    match name {
+        NO_GROUP => "No scope",
+        name => name,
+    }
+}
+
+fn write_markdown<W: Write>(writer: &mut W, tasks: Vec<Task>) -> io::Result<()> {
+    for (i, group) in group_tasks(tasks, GroupBy::Scope)
+        .groups
+        .into_iter()
+        .enumerate()
+    {
+        if i > 0 {
+            writeln!(writer)?;
+        }
+        writeln!(writer, "## {}\n", scope_heading(&group.name))?;
+        for task in group.tasks {
+            let check = match task.completed_at {
+                Some(_) => 'x',
+                None => ' ',
+            };
+            let mut details = vec![task.display_id()];
+            if let Some(due_at) = task.due_at {
+                details.push(format!("due {}", due_at.format("%Y-%m-%d")));
+            }
+            writeln!(
+                writer,
+                "- [{}] {} ({})",
+                check,
+                single_line(&task.description),
+                details.join(", ")
+            )?;
+        }
+    }
+    Ok(())
+}
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 2em; }
+th, td { border-bottom: 1px solid #ddd; padding: 0.4em 0.6em; text-align: left; }
+th { background: #f4f4f4; }
+tr.done td { color: #888; text-decoration: line-through; }
+tr.overdue td { color: #b00020; }";
+
+fn write_html<W: Write>(writer: &mut W, tasks: Vec<Task>, now: DateTime<Local>) -> io::Result<()> {
+    let done = tasks.iter().filter(|t| t.completed_at.is_some()).count();
+    writeln!(writer, "<!DOCTYPE html>")?;
+    writeln!(writer, "<html lang=\"en\">")?;
+    writeln!(writer, "<head>")?;
+    writeln!(writer, "<meta charset=\"utf-8\">")?;
+    writeln!(writer, "<title>Tasks</title>")?;
+    writeln!(writer, "<style>\n{}\n</style>", HTML_STYLE)?;
+    writeln!(writer, "</head>")?;
+    writeln!(writer, "<body>")?;
+    writeln!(writer, "<h1>Tasks</h1>")?;
+    writeln!(
+        writer,
+        "<p>{} tasks, {} open and {} done, exported on {}.</p>",
+        tasks.len(),
+        tasks.len() - done,
+        done,
+        now.format("%Y-%m-%d %H:%M")
+    )?;
+    for group in group_tasks(tasks, GroupBy::Scope).groups {
+        writeln!(
+            writer,
+            "<h2>{} ({})</h2>",
+            escape_html(scope_heading(&group.name)),
+            group.count
+        )?;
+        writeln!(writer, "<table>")?;
+        writeln!(
+            writer,
+            "<tr><th>ID</th><th>Description</th><th>Status</th><th>Created</th><th>Due</th><th>Completed</th></tr>"
+        )?;
+        for task in group.tasks {
+            let class = match task.completed_at {
+                _ if task.is_overdue(now) => " class=\"overdue\"",
+                Some(_) => " class=\"done\"",
+                None => "",
+            };
+            let format_date = |date: Option<DateTime<Local>>| {
+                date.map_or(String::new(), |d| d.format("%Y-%m-%d").to_string())
+            };
+            writeln!(
+                writer,
+                "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                class,
+                escape_html(&task.display_id()),
+                escape_html(&task.description),
+                task.status(),
+                task.created_at.format("%Y-%m-%d"),
+                format_date(task.due_at),
+                format_date(task.completed_at)
+            )?;
+        }
+        writeln!(writer, "</table>")?;
+    }
+    writeln!(writer, "</body>")?;
+    writeln!(writer, "</html>")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{Local, TimeZone};
+
+    use crate::domain::Task;
+
+    use super::{escape_html, write_export, ExportFormat};
+
+    fn tasks() -> Vec<Task> {
+        let at = |day| Local.with_ymd_and_hms(2026, 10, day, 9, 0, 0).unwrap();
+        vec![
+            Task::test(1, "Deploy the API")
+                .set_scope(Some("ops"))
+                .set_created_at(at(12))
+                .set_completed_at(Some(at(19))),
+            Task::test(2, "Plan\nretro")
+                .set_created_at(at(13))
+                .set_due_at(Some(at(22))),
+            Task::test(3, "Pay invoice")
+                .set_scope(Some("home office"))
+                .set_created_at(at(14)),
+        ]
+    }
+
+    fn export(format: ExportFormat) -> String {
+        let now = Local.with_ymd_and_hms(2026, 10, 19, 12, 0, 0).unwrap();
+        let mut buffer = Vec::new();
+        write_export(&mut buffer, format, tasks(), &HashMap::new(), now).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn todotxt_lines_carry_dates_and_projects() {
+        assert_eq!(
+            export(ExportFormat::Todotxt),
+            "x 2026-10-19 2026-10-12 Deploy the API +ops
+2026-10-13 Plan retro due:2026-10-22
+2026-10-14 Pay invoice +home-office
+"
+        );
+    }
+
+    #[test]
+    fn markdown_checklists_are_grouped_by_scope() {
+        assert_eq!(
+            export(ExportFormat::Markdown),
+            "## home office
+
+- [ ] Pay invoice (3)
+
+## ops
+
+- [x] Deploy the API (1)
+
+## No scope
+
+- [ ] Plan retro (2, due 2026-10-22)
+"
+        );
+    }
+
+    #[test]
+    fn html_reports_escape_descriptions() {
+        assert_eq!(
+            escape_html("<b>Tom & \"Jerry\"</b>"),
+            "&lt;b&gt;Tom &amp; &quot;Jerry&quot;&lt;/b&gt;"
+        );
+        let html = export(ExportFormat::Html);
+        assert!(html.contains("<p>3 tasks, 2 open and 1 done, exported on 2026-10-19 12:00.</p>"));
+        assert!(html.contains("<tr class=\"done\"><td>1</td><td>Deploy the API</td>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+}
@@ -0,0 +1,10 @@
+mod ast;
+mod error;
+mod lexer;
+mod parser;
+mod sql;
+
+pub use ast::{Condition, Field, FilterExpr, Operator, Value};
+pub use error::FilterError;
+pub use parser::parse_filter;
+pub use sql::push_filter;
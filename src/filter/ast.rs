@@ -0,0 +1,77 @@
+use chrono::{Duration, NaiveDate};
+
+#[derive(Debug, PartialEq)]
+pub enum FilterExpr {
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Condition(Condition),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Condition {
+    Done,
+    Open,
+    Compare {
+        field: Field,
+        operator: Operator,
+        value: Value,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    Id,
+    Scope,
+    Description,
+    Created,
+    Completed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Contains,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    None,
+    Number(u32),
+    Text(String),
+    Day(NaiveDate),
+    Age(Duration),
+}
+
+impl Field {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "id" => Some(Self::Id),
+            "scope" => Some(Self::Scope),
+            "desc" | "description" => Some(Self::Description),
+            "created" => Some(Self::Created),
+            "completed" => Some(Self::Completed),
+            _ => None,
+        }
+    }
+}
+
+impl Operator {
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            ":" | "=" => Some(Self::Equal),
+            "!=" => Some(Self::NotEqual),
+            "<" => Some(Self::Less),
+            "<=" => Some(Self::LessOrEqual),
+            ">" => Some(Self::Greater),
+            ">=" => Some(Self::GreaterOrEqual),
+            "~" => Some(Self::Contains),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+/// Filter parsing error pointing at the offending character of the expression
+#[derive(Debug, PartialEq)]
+pub struct FilterError {
+    pub expression: String,
+    pub position: usize,
+    pub message: String,
+}
+
+impl FilterError {
+    pub fn new(expression: &str, position: usize, message: impl Into<String>) -> Self {
+        Self {
+            expression: expression.to_string(),
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for FilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "invalid filter at column {}: {}",
+            self.position + 1,
+            self.message
+        )?;
+        writeln!(f, "  {}", self.expression)?;
+        write!(f, "  {}^", " ".repeat(self.position))
+    }
+}
+
+impl std::error::Error for FilterError {}
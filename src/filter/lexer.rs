@@ -0,0 +1,90 @@
+use super::FilterError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    OpenParen,
+    CloseParen,
+    Operator(String),
+    Word(String),
+    Quoted(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Character offset of the token in the expression
+    pub position: usize,
+}
+
+const OPERATOR_CHARS: [char; 6] = [':', '=', '!', '<', '>', '~'];
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !OPERATOR_CHARS.contains(&c) && !matches!(c, '(' | ')' | '"')
+}
+
+pub fn tokenize(expression: &str) -> Result<Vec<Token>, FilterError> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let kind = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => {
+                i += 1;
+                TokenKind::OpenParen
+            }
+            ')' => {
+                i += 1;
+                TokenKind::CloseParen
+            }
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => {
+                            return Err(FilterError::new(expression, start, "unterminated string"))
+                        }
+                        Some('"') => break,
+                        Some('\\') if chars.get(i + 1).is_some() => {
+                            text.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(c) => {
+                            text.push(*c);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                TokenKind::Quoted(text)
+            }
+            c if OPERATOR_CHARS.contains(&c) => {
+                let symbol: String = chars[i..]
+                    .iter()
+                    .take_while(|c| OPERATOR_CHARS.contains(c))
+                    .collect();
+                i += symbol.chars().count();
+                TokenKind::Operator(symbol)
+            }
+            _ => {
+                let word: String = chars[i..]
+                    .iter()
+                    .take_while(|c| is_word_char(**c))
+                    .collect();
+                i += word.chars().count();
+                TokenKind::Word(word)
+            }
+        };
+        tokens.push(Token {
+            kind,
+            position: start,
+        });
+    }
+    Ok(tokens)
+}
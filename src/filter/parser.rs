@@ -0,0 +1,278 @@
+use chrono::Local;
+
+use crate::dates::DateSpec;
+
+use super::{
+    lexer::{tokenize, Token, TokenKind},
+    Condition, Field, FilterError, FilterExpr, Operator, Value,
+};
+
+/// Parses expressions such as `scope:work and not done and (created<7d or desc~"deploy")`
+pub fn parse_filter(expression: &str) -> Result<FilterExpr, FilterError> {
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser {
+        expression,
+        tokens,
+        index: 0,
+    };
+    let expr = parser.parse_or()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) => {
+            Err(parser.error_at(token.position, "expected 'and', 'or' or end of filter"))
+        }
+    }
+}
+
+struct Parser<'a> {
+    expression: &'a str,
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<(TokenKind, usize)> {
+        let token = self
+            .tokens
+            .get(self.index)
+            .map(|t| (t.kind.clone(), t.position));
+        self.index += 1;
+        token
+    }
+
+    fn end_position(&self) -> usize {
+        self.expression.chars().count()
+    }
+
+    fn error_at(&self, position: usize, message: impl Into<String>) -> FilterError {
+        FilterError::new(self.expression, position, message)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token { kind: TokenKind::Word(w), .. }) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut expr = self.parse_and()?;
+        while self.peek_keyword("or") {
+            self.index += 1;
+            let right = self.parse_and()?;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(right));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, FilterError> {
+        let mut expr = self.parse_not()?;
+        while self.peek_keyword("and") {
+            self.index += 1;
+            let right = self.parse_not()?;
+            expr = FilterExpr::And(Box::new(expr), Box::new(right));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<FilterExpr, FilterError> {
+        if self.peek_keyword("not") {
+            self.index += 1;
+            let expr = self.parse_not()?;
+            return Ok(FilterExpr::Not(Box::new(expr)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<FilterExpr, FilterError> {
+        let end = self.end_position();
+        let (kind, position) = match self.next() {
+            Some(token) => token,
+            None => return Err(self.error_at(end, "unexpected end of filter")),
+        };
+        match kind {
+            TokenKind::OpenParen => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some((TokenKind::CloseParen, _)) => Ok(expr),
+                    _ => Err(self.error_at(position, "unclosed parenthesis")),
+                }
+            }
+            TokenKind::Word(word) => {
+                let word = word.to_lowercase();
+                self.parse_condition(&word, position)
+                    .map(FilterExpr::Condition)
+            }
+            _ => Err(self.error_at(position, "expected a condition")),
+        }
+    }
+
+    fn parse_condition(&mut self, name: &str, position: usize) -> Result<Condition, FilterError> {
+        let operator = match self.peek() {
+            Some(Token {
+                kind: TokenKind::Operator(symbol),
+                position,
+            }) => Some((symbol.clone(), *position)),
+            _ => None,
+        };
+        let Some((symbol, operator_position)) = operator else {
+            return match name {
+                "done" => Ok(Condition::Done),
+                "open" => Ok(Condition::Open),
+                _ => Err(self.error_at(position, format!("unknown keyword '{}'", name))),
+            };
+        };
+        let field = Field::from_name(name)
+            .ok_or_else(|| self.error_at(position, format!("unknown field '{}'", name)))?;
+        let operator = Operator::from_symbol(&symbol).ok_or_else(|| {
+            self.error_at(operator_position, format!("unknown operator '{}'", symbol))
+        })?;
+        self.index += 1;
+
+        let end = self.end_position();
+        let (raw_value, value_position) = match self.next() {
+            Some((TokenKind::Word(value) | TokenKind::Quoted(value), position)) => {
+                (value, position)
+            }
+            Some((_, position)) => return Err(self.error_at(position, "expected a value")),
+            None => return Err(self.error_at(end, "expected a value")),
+        };
+        let value = parse_value(field, &raw_value).ok_or_else(|| {
+            self.error_at(value_position, invalid_value_message(field, &raw_value))
+        })?;
+        if !is_supported(field, operator, &value) {
+            return Err(self.error_at(
+                operator_position,
+                format!("operator '{}' is not supported here", symbol),
+            ));
+        }
+        Ok(Condition::Compare {
+            field,
+            operator,
+            value,
+        })
+    }
+}
+
+fn parse_value(field: Field, value: &str) -> Option<Value> {
+    match field {
+        Field::Id => value.parse().ok().map(Value::Number),
+        Field::Scope if value.eq_ignore_ascii_case("none") => Some(Value::None),
+        Field::Scope => Some(Value::Text(value.to_lowercase())),
+        Field::Description => Some(Value::Text(value.to_string())),
+        Field::Created | Field::Completed => {
+            let now = Local::now();
+            match DateSpec::parse(value, now.date_naive())
+                .filter(|spec| spec.resolve(now).is_some())?
+            {
+                DateSpec::Ago(duration) => Some(Value::Age(duration)),
+                DateSpec::Day(day) => Some(Value::Day(day)),
+            }
+        }
+    }
+}
+
+fn invalid_value_message(field: Field, value: &str) -> String {
+    let expected = match field {
+        Field::Id => "a task id",
+        Field::Scope | Field::Description => "a text",
+        Field::Created | Field::Completed => {
+            "a date (YYYY-MM-DD, today, yesterday) or an age (12h, 7d, 2w)"
+        }
+    };
+    format!("invalid value '{}', expected {}", value, expected)
+}
+
+fn is_supported(field: Field, operator: Operator, value: &Value) -> bool {
+    match (field, operator, value) {
+        (_, Operator::Equal | Operator::NotEqual, Value::Age(_)) => false,
+        (_, Operator::Equal | Operator::NotEqual, _) => true,
+        (_, _, Value::None) => false,
+        (Field::Scope | Field::Description, Operator::Contains, _) => true,
+        (Field::Scope | Field::Description, _, _) => false,
+        (_, Operator::Contains, _) => false,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate};
+
+    use crate::filter::{Condition, Field, FilterExpr, Operator, Value};
+
+    use super::parse_filter;
+
+    fn compare(field: Field, operator: Operator, value: Value) -> FilterExpr {
+        FilterExpr::Condition(Condition::Compare {
+            field,
+            operator,
+            value,
+        })
+    }
+
+    #[test]
+    fn conditions_are_combined_with_precedence() {
+        let expr = parse_filter(r#"scope:Work and not done and (created<7d or desc~"deploy it")"#)
+            .unwrap();
+        let expected = FilterExpr::And(
+            Box::new(FilterExpr::And(
+                Box::new(compare(
+                    Field::Scope,
+                    Operator::Equal,
+                    Value::Text("work".to_string()),
+                )),
+                Box::new(FilterExpr::Not(Box::new(FilterExpr::Condition(
+                    Condition::Done,
+                )))),
+            )),
+            Box::new(FilterExpr::Or(
+                Box::new(compare(
+                    Field::Created,
+                    Operator::Less,
+                    Value::Age(Duration::days(7)),
+                )),
+                Box::new(compare(
+                    Field::Description,
+                    Operator::Contains,
+                    Value::Text("deploy it".to_string()),
+                )),
+            )),
+        );
+        assert_eq!(expected, expr);
+    }
+
+    #[test]
+    fn dates_and_ids_are_parsed() {
+        let expr = parse_filter("completed>=2024-08-01 or id!=3").unwrap();
+        let expected = FilterExpr::Or(
+            Box::new(compare(
+                Field::Completed,
+                Operator::GreaterOrEqual,
+                Value::Day(NaiveDate::from_ymd_opt(2024, 8, 1).unwrap()),
+            )),
+            Box::new(compare(Field::Id, Operator::NotEqual, Value::Number(3))),
+        );
+        assert_eq!(expected, expr);
+    }
+
+    #[test]
+    fn errors_point_at_offending_column() {
+        let cases = [
+            ("scope:work and", 14),
+            ("scope:work done", 11),
+            ("owner:me", 0),
+            ("created:7d", 7),
+            ("id~3", 2),
+            ("created<soon", 8),
+            ("created<100000000d", 8),
+            ("(done or open", 0),
+            ("desc:\"unterminated", 5),
+        ];
+        for (expression, position) in cases {
+            let error = parse_filter(expression).unwrap_err();
+            assert_eq!(position, error.position, "{}", expression);
+        }
+    }
+}
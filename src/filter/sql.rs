@@ -0,0 +1,183 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use sqlx::{QueryBuilder, Sqlite};
+
+use crate::dates::start_of_day;
+
+use super::{Condition, Field, FilterExpr, Operator, Value};
+
+/// Appends the filter as a parameterized SQL predicate over the `tasks` table
+pub fn push_filter(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    expr: &FilterExpr,
+    now: DateTime<Local>,
+) -> anyhow::Result<()> {
+    match expr {
+        FilterExpr::And(left, right) => push_binary(builder, left, "AND", right, now),
+        FilterExpr::Or(left, right) => push_binary(builder, left, "OR", right, now),
+        FilterExpr::Not(inner) => {
+            builder.push("NOT (");
+            push_filter(builder, inner, now)?;
+            builder.push(")");
+            Ok(())
+        }
+        FilterExpr::Condition(condition) => push_condition(builder, condition, now),
+    }
+}
+
+fn push_binary(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    left: &FilterExpr,
+    operator: &str,
+    right: &FilterExpr,
+    now: DateTime<Local>,
+) -> anyhow::Result<()> {
+    builder.push("(");
+    push_filter(builder, left, now)?;
+    builder.push(format!(" {} ", operator));
+    push_filter(builder, right, now)?;
+    builder.push(")");
+    Ok(())
+}
+
+fn push_condition(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    condition: &Condition,
+    now: DateTime<Local>,
+) -> anyhow::Result<()> {
+    let (field, operator, value) = match condition {
+        Condition::Done => {
+            builder.push("tasks.completed_at IS NOT NULL");
+            return Ok(());
+        }
+        Condition::Open => {
+            builder.push("tasks.completed_at IS NULL");
+            return Ok(());
+        }
+        Condition::Compare {
+            field,
+            operator,
+            value,
+        } => (*field, *operator, value),
+    };
+    match (field, value) {
+        (Field::Id, Value::Number(id)) => {
+            builder.push(format!("tasks.id {} ", symbol(operator)));
+            builder.push_bind(*id);
+        }
+        (Field::Scope, Value::None) => {
+            builder.push(match operator {
+                Operator::Equal => "tasks.scope IS NULL",
+                _ => "tasks.scope IS NOT NULL",
+            });
+        }
+        (Field::Scope, Value::Text(text)) => push_text(builder, "tasks.scope", operator, text),
+        (Field::Description, Value::Text(text)) => {
+            push_text(builder, "tasks.description", operator, text)
+        }
+        (Field::Created, Value::Day(day)) => push_day(builder, "tasks.created_at", operator, *day),
+        (Field::Completed, Value::Day(day)) => {
+            push_day(builder, "tasks.completed_at", operator, *day)
+        }
+        (Field::Created, Value::Age(age)) => push_moment(
+            builder,
+            "tasks.created_at",
+            age_operator(operator),
+            moment_before(now, *age)?,
+        ),
+        (Field::Completed, Value::Age(age)) => push_moment(
+            builder,
+            "tasks.completed_at",
+            age_operator(operator),
+            moment_before(now, *age)?,
+        ),
+        _ => unreachable!("value type is validated while parsing"),
+    }
+    Ok(())
+}
+
+fn moment_before(now: DateTime<Local>, age: Duration) -> anyhow::Result<DateTime<Local>> {
+    now.checked_sub_signed(age)
+        .ok_or_else(|| anyhow!("Age of {} days is out of range", age.num_days()))
+}
+
+fn symbol(operator: Operator) -> &'static str {
+    match operator {
+        Operator::Equal => "=",
+        Operator::NotEqual => "!=",
+        Operator::Less => "<",
+        Operator::LessOrEqual => "<=",
+        Operator::Greater => ">",
+        Operator::GreaterOrEqual => ">=",
+        Operator::Contains => unreachable!("contains has no comparison symbol"),
+    }
+}
+
+/// An age below a duration means a moment after `now - duration`
+fn age_operator(operator: Operator) -> Operator {
+    match operator {
+        Operator::Less => Operator::Greater,
+        Operator::LessOrEqual => Operator::GreaterOrEqual,
+        Operator::Greater => Operator::Less,
+        Operator::GreaterOrEqual => Operator::LessOrEqual,
+        other => other,
+    }
+}
+
+fn push_text(builder: &mut QueryBuilder<'_, Sqlite>, column: &str, operator: Operator, text: &str) {
+    match operator {
+        Operator::Contains => builder.push(format!("instr(lower({}), lower(", column)),
+        Operator::NotEqual => builder.push(format!(
+            "({} IS NULL OR lower({}) != lower(",
+            column, column
+        )),
+        _ => builder.push(format!("lower({}) = lower(", column)),
+    };
+    builder.push_bind(text.to_string());
+    builder.push(match operator {
+        Operator::Contains => ")) > 0",
+        Operator::NotEqual => "))",
+        _ => ")",
+    });
+}
+
+fn push_moment(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    column: &str,
+    operator: Operator,
+    moment: DateTime<Local>,
+) {
+    builder.push(format!(
+        "datetime({}) {} datetime(",
+        column,
+        symbol(operator)
+    ));
+    builder.push_bind(moment.with_timezone(&Utc));
+    builder.push(")");
+}
+
+fn push_day(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    column: &str,
+    operator: Operator,
+    day: NaiveDate,
+) {
+    let start = start_of_day(day);
+    let end = start_of_day(day.succ_opt().unwrap_or(day));
+    match operator {
+        Operator::Equal | Operator::NotEqual => {
+            if operator == Operator::NotEqual {
+                builder.push("NOT ");
+            }
+            builder.push("(");
+            push_moment(builder, column, Operator::GreaterOrEqual, start);
+            builder.push(" AND ");
+            push_moment(builder, column, Operator::Less, end);
+            builder.push(")");
+        }
+        Operator::Less => push_moment(builder, column, Operator::Less, start),
+        Operator::LessOrEqual => push_moment(builder, column, Operator::Less, end),
+        Operator::Greater => push_moment(builder, column, Operator::GreaterOrEqual, end),
+        _ => push_moment(builder, column, Operator::GreaterOrEqual, start),
+    }
+}
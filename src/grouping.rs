@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+
+use chrono::Datelike;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::domain::{Task, TaskStatus};
+
+/// Property listed tasks are grouped by
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum GroupBy {
+    Scope,
+    Status,
+    /// Day the task is due
+    Due,
+    /// ISO week the task was created in, e.g. 2024-W32
+    CreatedWeek,
+}
+
+/// Property giving the lanes of a board
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum BoardLanes {
+    /// An open and a done lane, shown even when empty
+    Status,
+    /// A lane per scope having tasks
+    Scope,
+}
+
+/// Group of the tasks without a value for the grouped property
+pub const NO_GROUP: &str = "(none)";
+
+#[derive(Debug, Serialize)]
+pub struct TaskGroup {
+    pub name: String,
+    pub count: usize,
+    pub tasks: Vec<Task>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroupedTasks {
+    pub groups: Vec<TaskGroup>,
+    pub total: usize,
+}
+
+impl GroupBy {
+    /// Sort key and name of the group of a task
+    fn group_of(&self, task: &Task) -> Option<(String, String)> {
+        match self {
+            GroupBy::Scope => task.scope.as_ref().map(|s| (s.to_string(), s.to_string())),
+            GroupBy::Status => {
+                let order = u8::from(task.completed_at.is_some()).to_string();
+                Some((order, task.status().to_string()))
+            }
+            GroupBy::Due => task.due_at.map(|due_at| {
+                let day = due_at.format("%Y-%m-%d").to_string();
+                (day.clone(), day)
+            }),
+            GroupBy::CreatedWeek => {
+                let week = task.created_at.iso_week();
+                let name = format!("{}-W{:02}", week.year(), week.week());
+                Some((name.clone(), name))
+            }
+        }
+    }
+}
+
+/// Splits tasks in groups sorted by name, tasks keeping their order within a group and the
+/// ones without a group coming last
+pub fn group_tasks(tasks: Vec<Task>, group_by: GroupBy) -> GroupedTasks {
+    let total = tasks.len();
+    let mut groups: Vec<(Option<String>, TaskGroup)> = vec![];
+    for task in tasks {
+        let (key, name) = match group_by.group_of(&task) {
+            Some((key, name)) => (Some(key), name),
+            None => (None, NO_GROUP.to_string()),
+        };
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => {
+                group.count += 1;
+                group.tasks.push(task);
+            }
+            None => groups.push((
+                key,
+                TaskGroup {
+                    name,
+                    count: 1,
+                    tasks: vec![task],
+                },
+            )),
+        }
+    }
+    groups.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    GroupedTasks {
+        groups: groups.into_iter().map(|(_, group)| group).collect(),
+        total,
+    }
+}
+
+/// Splits tasks in the lanes of a board
+pub fn board_lanes(tasks: Vec<Task>, lanes: BoardLanes) -> GroupedTasks {
+    match lanes {
+        BoardLanes::Scope => group_tasks(tasks, GroupBy::Scope),
+        BoardLanes::Status => {
+            let total = tasks.len();
+            let (done, open): (Vec<Task>, Vec<Task>) =
+                tasks.into_iter().partition(|t| t.completed_at.is_some());
+            let groups = [(TaskStatus::Open, open), (TaskStatus::Done, done)]
+                .into_iter()
+                .map(|(status, tasks)| TaskGroup {
+                    name: status.to_string(),
+                    count: tasks.len(),
+                    tasks,
+                })
+                .collect();
+            GroupedTasks { groups, total }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeZone};
+
+    use crate::domain::Task;
+
+    use super::{board_lanes, group_tasks, BoardLanes, GroupBy};
+
+    fn task(id: u32, scope: Option<&str>, day: u32, done: bool) -> Task {
+        let created_at = Local.with_ymd_and_hms(2024, 8, day, 10, 0, 0).unwrap();
+        Task::test(id, &format!("Task {}", id))
+            .set_scope(scope)
+            .set_created_at(created_at)
+            .set_completed_at(done.then_some(created_at))
+    }
+
+    fn summary(group_by: GroupBy) -> Vec<(String, Vec<String>)> {
+        let tasks = vec![
+            task(1, Some("work"), 5, false),
+            task(2, None, 6, true),
+            task(3, Some("home"), 12, false),
+            task(4, Some("work"), 13, true),
+        ];
+        let grouped = group_tasks(tasks, group_by);
+        assert_eq!(grouped.total, 4);
+        grouped
+            .groups
+            .into_iter()
+            .map(|g| {
+                assert_eq!(g.count, g.tasks.len());
+                (g.name, g.tasks.iter().map(|t| t.id.to_string()).collect())
+            })
+            .collect()
+    }
+
+    fn groups(groups: &[(&str, &[&str])]) -> Vec<(String, Vec<String>)> {
+        groups
+            .iter()
+            .map(|(name, ids)| {
+                (
+                    name.to_string(),
+                    ids.iter().map(|id| id.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tasks_without_a_value_are_grouped_last() {
+        assert_eq!(
+            summary(GroupBy::Scope),
+            groups(&[("home", &["3"]), ("work", &["1", "4"]), ("(none)", &["2"])])
+        );
+        assert_eq!(
+            summary(GroupBy::Due),
+            groups(&[("(none)", &["1", "2", "3", "4"])])
+        );
+    }
+
+    #[test]
+    fn tasks_are_grouped_by_status_and_week() {
+        assert_eq!(
+            summary(GroupBy::Status),
+            groups(&[("open", &["1", "3"]), ("done", &["2", "4"])])
+        );
+        assert_eq!(
+            summary(GroupBy::CreatedWeek),
+            groups(&[("2024-W32", &["1", "2"]), ("2024-W33", &["3", "4"])])
+        );
+    }
+
+    #[test]
+    fn status_lanes_are_kept_when_empty() {
+        let tasks = vec![task(1, None, 5, false), task(2, Some("work"), 6, false)];
+        let lanes: Vec<(String, usize)> = board_lanes(tasks, BoardLanes::Status)
+            .groups
+            .into_iter()
+            .map(|lane| (lane.name, lane.count))
+            .collect();
+        assert_eq!(
+            vec![("open".to_string(), 2), ("done".to_string(), 0)],
+            lanes
+        );
+    }
+}
@@ -0,0 +1,445 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+
+use crate::{
+    dates::start_of_day,
+    domain::{NewTask, Scope, Task},
+    taskwarrior::{is_uuid, new_uuid, TaskwarriorData},
+};
+
+/// Longest line allowed by RFC 5545, in bytes, longer lines being folded
+const MAX_LINE_LENGTH: usize = 75;
+
+const UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Writes tasks as a calendar of VTODO components.
+///
+/// Their UID is the one of the VTODO they were imported from, or else the uuid saved in the
+/// database, so calendar clients update the tasks they already know when the calendar is
+/// exported again.
+pub fn write_icalendar<W: Write>(
+    writer: &mut W,
+    tasks: &[Task],
+    data: &HashMap<u32, TaskwarriorData>,
+    now: DateTime<Local>,
+) -> io::Result<()> {
+    write_line(writer, "BEGIN:VCALENDAR")?;
+    write_line(writer, "VERSION:2.0")?;
+    write_line(writer, "PRODID:-//tasks-rs//tasks-cli//EN")?;
+    for task in tasks {
+        let id: u32 = task.id.clone().into();
+        let uuid = data
+            .get(&id)
+            .and_then(|data| data.calendar_uid.clone().or_else(|| data.uuid.clone()))
+            .unwrap_or_else(new_uuid);
+        write_line(writer, "BEGIN:VTODO")?;
+        write_line(writer, &format!("UID:{}", escape_text(&uuid)))?;
+        write_line(writer, &format!("DTSTAMP:{}", format_utc(now)))?;
+        write_line(
+            writer,
+            &format!("SUMMARY:{}", escape_text(&task.description)),
+        )?;
+        if let Some(scope) = task.scope.as_ref() {
+            write_line(
+                writer,
+                &format!("CATEGORIES:{}", escape_text(scope.as_ref())),
+            )?;
+        }
+        write_line(writer, &format!("CREATED:{}", format_utc(task.created_at)))?;
+        match task.completed_at {
+            Some(completed_at) => {
+                write_line(writer, &format!("COMPLETED:{}", format_utc(completed_at)))?;
+                write_line(writer, "STATUS:COMPLETED")?;
+            }
+            None => write_line(writer, "STATUS:NEEDS-ACTION")?,
+        }
+        if let Some(due_at) = task.due_at {
+            write_line(
+                writer,
+                &format!("DUE;VALUE=DATE:{}", due_at.format("%Y%m%d")),
+            )?;
+        }
+        write_line(writer, "END:VTODO")?;
+    }
+    write_line(writer, "END:VCALENDAR")
+}
+
+/// Writes a content line, folded into lines of at most 75 bytes ending with CRLF
+fn write_line<W: Write>(writer: &mut W, line: &str) -> io::Result<()> {
+    let mut rest = line;
+    let mut limit = MAX_LINE_LENGTH;
+    loop {
+        if rest.len() <= limit {
+            return write!(writer, "{}\r\n", rest);
+        }
+        let mut end = limit;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        write!(writer, "{}\r\n ", &rest[..end])?;
+        rest = &rest[end..];
+        // Continuation lines start with a space
+        limit = MAX_LINE_LENGTH - 1;
+    }
+}
+
+fn format_utc(date: DateTime<Local>) -> String {
+    date.naive_utc().format(UTC_FORMAT).to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n' | 'N') => unescaped.push('\n'),
+                Some(c) => unescaped.push(c),
+                None => {}
+            },
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// Property of a component, such as `DUE;VALUE=DATE:20261022`
+struct Property<'a> {
+    name: String,
+    /// Parameters such as `VALUE=DATE` or `TZID=Europe/Paris`, with unquoted values
+    parameters: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl Property<'_> {
+    fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+fn parse_property(line: &str) -> Option<Property<'_>> {
+    // Quoted parameter values may contain colons and semicolons
+    let mut quoted = false;
+    let mut separators = vec![];
+    let mut value_start = None;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => separators.push(i),
+            ':' if !quoted => {
+                value_start = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let value_start = value_start?;
+    separators.push(value_start);
+    let name = &line[..separators[0]];
+    let parameters = separators
+        .windows(2)
+        .filter_map(|bounds| {
+            let (name, value) = line[bounds[0] + 1..bounds[1]].split_once('=')?;
+            Some((name.to_uppercase(), value.trim_matches('"')))
+        })
+        .collect();
+    Some(Property {
+        name: name.to_uppercase(),
+        parameters,
+        value: &line[value_start + 1..],
+    })
+}
+
+/// Reads the VTODO components of a calendar, with the line each of them starts on and the
+/// warnings raised while reading them
+pub fn parse_vtodos(content: &str) -> Vec<(usize, Result<NewTask, String>, Vec<String>)> {
+    let mut todos = vec![];
+    let mut current: Option<(usize, Vec<String>)> = None;
+    for (number, line) in unfold_lines(content) {
+        match (line.to_uppercase().as_str(), current.as_mut()) {
+            ("BEGIN:VTODO", _) => current = Some((number, vec![])),
+            ("END:VTODO", Some(_)) => {
+                let (start, lines) = current.take().expect("a VTODO is open");
+                let mut warnings = vec![];
+                let task = parse_vtodo(&lines, &mut warnings);
+                todos.push((start, task, warnings));
+            }
+            (_, Some((_, lines))) => lines.push(line),
+            (_, None) => {}
+        }
+    }
+    todos
+}
+
+/// Joins folded lines, numbering them after the first line they span
+fn unfold_lines(content: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = vec![];
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some((_, previous))) => previous.push_str(continuation),
+            _ => lines.push((i + 1, line.to_string())),
+        }
+    }
+    lines
+}
+
+fn parse_vtodo(lines: &[String], warnings: &mut Vec<String>) -> Result<NewTask, String> {
+    let mut task = NewTask::default();
+    let mut completed = false;
+    // Properties of nested components, such as alarms, do not describe the task
+    let mut depth = 0;
+    for property in lines.iter().filter_map(|line| parse_property(line)) {
+        match property.name.as_str() {
+            "BEGIN" => depth += 1,
+            "END" => depth -= 1,
+            _ if depth > 0 => {}
+            "UID" => {
+                // Only uuids are shared with Taskwarrior, which rejects other identifiers
+                let uid = unescape_text(property.value);
+                match is_uuid(&uid) {
+                    true => task.uuid = Some(uid.to_lowercase()),
+                    false => task.calendar_uid = Some(uid),
+                }
+            }
+            "SUMMARY" => task.description = unescape_text(property.value),
+            "CATEGORIES" => {
+                task.scope = split_categories(property.value)
+                    .into_iter()
+                    .next()
+                    .map(Scope::new)
+            }
+            "CREATED" => task.created_at = Some(parse_date(&property, warnings)?),
+            "COMPLETED" => task.completed_at = Some(parse_date(&property, warnings)?),
+            "DUE" => task.due_at = Some(parse_date(&property, warnings)?),
+            "STATUS" => match property.value.to_uppercase().as_str() {
+                "COMPLETED" => completed = true,
+                "CANCELLED" => return Err("cancelled in the calendar".to_string()),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    if task.description.trim().is_empty() {
+        return Err("missing SUMMARY".to_string());
+    }
+    if completed && task.completed_at.is_none() {
+        task.completed_at = Some(Local::now());
+    }
+    Ok(task)
+}
+
+/// Categories of a CATEGORIES value, separated by unescaped commas
+fn split_categories(value: &str) -> Vec<String> {
+    let mut categories = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            ',' if !escaped => {
+                categories.push(unescape_text(&value[start..i]));
+                start = i + 1;
+            }
+            _ => escaped = false,
+        }
+    }
+    categories.push(unescape_text(&value[start..]));
+    categories.retain(|category| !category.trim().is_empty());
+    categories
+}
+
+/// Reads a UTC or floating date-time, or a date, the last two in local time.
+///
+/// The rules of time zones other than UTC are not known here, so their date-times are read
+/// as local time, with a warning.
+fn parse_date(property: &Property, warnings: &mut Vec<String>) -> Result<DateTime<Local>, String> {
+    let value = property.value.trim();
+    // Date-times with a UTC time zone are written without the trailing Z
+    let in_utc = match property.parameter("TZID") {
+        None => false,
+        Some("UTC" | "Etc/UTC" | "GMT" | "Etc/GMT") => true,
+        Some(zone) => {
+            warnings.push(format!(
+                "{} in time zone '{}' read as local time",
+                property.name, zone
+            ));
+            false
+        }
+    };
+    let utc = NaiveDateTime::parse_from_str(value, UTC_FORMAT)
+        .ok()
+        .map(|date| date.and_utc().with_timezone(&Local));
+    let local = || {
+        let date = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+        match in_utc {
+            true => Some(date.and_utc().with_timezone(&Local)),
+            false => Local.from_local_datetime(&date).earliest(),
+        }
+    };
+    let day = || {
+        NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(start_of_day)
+    };
+    utc.or_else(local)
+        .or_else(day)
+        .ok_or(format!("invalid {} '{}'", property.name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{Local, TimeZone, Utc};
+
+    use crate::{
+        domain::{Scope, Task},
+        taskwarrior::TaskwarriorData,
+    };
+
+    use super::{parse_vtodos, write_icalendar, write_line};
+
+    #[test]
+    fn tasks_are_written_as_vtodos() {
+        let created_at = Utc.with_ymd_and_hms(2026, 10, 12, 9, 0, 0).unwrap();
+        let task = Task::test(7, "Plan retro; bring snacks, cake")
+            .set_scope(Some("team"))
+            .set_created_at(created_at.with_timezone(&Local))
+            .set_due_at(Some(Local.with_ymd_and_hms(2026, 10, 22, 0, 0, 0).unwrap()));
+        let mut buffer = Vec::new();
+        let now = created_at.with_timezone(&Local);
+        let data = HashMap::from([(
+            7,
+            TaskwarriorData {
+                uuid: Some("5f2d7c9e-1b2a-4c3d-9e8f-0a1b2c3d4e5f".to_string()),
+                ..Default::default()
+            },
+        )]);
+        write_icalendar(&mut buffer, &[task], &data, now).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//tasks-rs//tasks-cli//EN\r
+BEGIN:VTODO\r
+UID:5f2d7c9e-1b2a-4c3d-9e8f-0a1b2c3d4e5f\r
+DTSTAMP:20261012T090000Z\r
+SUMMARY:Plan retro\\; bring snacks\\, cake\r
+CATEGORIES:team\r
+CREATED:20261012T090000Z\r
+STATUS:NEEDS-ACTION\r
+DUE;VALUE=DATE:20261022\r
+END:VTODO\r
+END:VCALENDAR\r
+"
+        );
+    }
+
+    #[test]
+    fn long_lines_are_folded_between_characters() {
+        let mut buffer = Vec::new();
+        write_line(&mut buffer, &format!("SUMMARY:{}", "é".repeat(40))).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = text.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].len() <= 75 && lines[1].len() <= 75);
+        assert_eq!(
+            lines[0].to_string() + &lines[1][1..],
+            format!("SUMMARY:{}", "é".repeat(40))
+        );
+    }
+
+    #[test]
+    fn vtodos_are_read_back() {
+        let calendar = "BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+SUMMARY:Not a task\r
+END:VEVENT\r
+BEGIN:VTODO\r
+UID:abc-123\r
+SUMMARY:Pay invoice\\, then\r
+  archive it\r
+CATEGORIES:Home,Money\r
+CREATED:20261012T090000Z\r
+DUE:20261020T170000\r
+STATUS:COMPLETED\r
+COMPLETED:20261019T100000Z\r
+BEGIN:VALARM\r
+SUMMARY:Reminder\r
+END:VALARM\r
+END:VTODO\r
+BEGIN:VTODO\r
+SUMMARY:Call\r
+DUE;TZID=Europe/Paris:20261020T170000\r
+END:VTODO\r
+BEGIN:VTODO\r
+SUMMARY:Review\r
+DUE;TZID=\"Etc/UTC\":20261020T170000\r
+END:VTODO\r
+BEGIN:VTODO\r
+DUE:someday\r
+END:VTODO\r
+END:VCALENDAR\r
+";
+        let todos = parse_vtodos(calendar);
+        assert_eq!(todos.len(), 4);
+        let (line, task, _) = &todos[0];
+        let task = task.as_ref().unwrap();
+        assert_eq!(*line, 5);
+        assert_eq!(task.description, "Pay invoice, then archive it");
+        assert_eq!(task.scope, Some(Scope::new("home".to_string())));
+        assert_eq!(task.calendar_uid.as_deref(), Some("abc-123"));
+        assert!(task.uuid.is_none());
+        assert_eq!(
+            task.completed_at.unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 19, 10, 0, 0).unwrap()
+        );
+        assert_eq!(
+            task.due_at.unwrap().naive_local().to_string(),
+            "2026-10-20 17:00:00"
+        );
+        let (_, task, warnings) = &todos[1];
+        assert_eq!(
+            task.as_ref()
+                .unwrap()
+                .due_at
+                .unwrap()
+                .naive_local()
+                .to_string(),
+            "2026-10-20 17:00:00"
+        );
+        assert_eq!(
+            warnings,
+            &["DUE in time zone 'Europe/Paris' read as local time"]
+        );
+        assert_eq!(
+            todos[2].1.as_ref().unwrap().due_at.unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 20, 17, 0, 0).unwrap()
+        );
+        assert_eq!(todos[3].0, 26);
+        assert_eq!(todos[3].1.as_ref().unwrap_err(), "invalid DUE 'someday'");
+    }
+}
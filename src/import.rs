@@ -0,0 +1,296 @@
+use std::fmt::Display;
+
+use anyhow::Context;
+use chrono::{DateTime, Local, NaiveDate};
+use clap::ValueEnum;
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{
+    dates::{parse_datetime, start_of_day},
+    domain::{NewTask, Scope},
+    icalendar::parse_vtodos,
+    taskwarrior::TaskwarriorTask,
+};
+
+/// Formats of the task lists that can be imported
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    /// todo.txt lines, their first +project becoming the scope
+    Todotxt,
+    /// Comma separated values with a header row naming the fields
+    Csv,
+    /// An array of task objects, or one object per line
+    Json,
+    /// Output of `task export`, keeping uuids to update the tasks when imported again
+    Taskwarrior,
+    /// VTODO components of an iCalendar file, their UID updating tasks imported before
+    Ics,
+}
+
+/// Task that could not be imported, or was imported with a caveat
+#[derive(Debug, PartialEq)]
+pub struct ImportError {
+    /// Line of the file, or position of the task in a JSON array
+    pub location: String,
+    pub message: String,
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+/// Tasks read from a file, along with the ones that could not be read
+#[derive(Debug, Default)]
+pub struct ParsedImport {
+    pub tasks: Vec<NewTask>,
+    pub errors: Vec<ImportError>,
+    /// Parts of tasks read differently than written, such as dates in unknown time zones
+    pub warnings: Vec<ImportError>,
+}
+
+impl ParsedImport {
+    fn push(&mut self, location: String, result: Result<NewTask, String>) {
+        match result {
+            Ok(task) => self.tasks.push(task),
+            Err(message) => self.errors.push(ImportError { location, message }),
+        }
+    }
+}
+
+/// Reads the tasks of a file, skipping the invalid ones.
+///
+/// Fails only when the file as a whole cannot be read, such as a malformed JSON array.
+pub fn parse_import(content: &str, format: ImportFormat) -> anyhow::Result<ParsedImport> {
+    let mut parsed = ParsedImport::default();
+    match format {
+        ImportFormat::Todotxt => {
+            for (i, line) in content.lines().enumerate() {
+                if !line.trim().is_empty() {
+                    parsed.push(format!("line {}", i + 1), parse_todotxt_line(line));
+                }
+            }
+        }
+        ImportFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .flexible(true)
+                .from_reader(content.as_bytes());
+            let headers = reader.headers().context("Invalid CSV header")?.clone();
+            for record in reader.records() {
+                let (line, result) = match record {
+                    Ok(record) => (
+                        record.position().map_or(0, |position| position.line()),
+                        record
+                            .deserialize::<ImportedTask>(Some(&headers))
+                            .map_err(|e| e.to_string())
+                            .and_then(NewTask::try_from),
+                    ),
+                    Err(e) => (
+                        e.position().map_or(0, |position| position.line()),
+                        Err(e.to_string()),
+                    ),
+                };
+                parsed.push(format!("line {}", line), result);
+            }
+        }
+        ImportFormat::Json => parse_json::<ImportedTask>(content, &mut parsed)?,
+        ImportFormat::Taskwarrior => parse_json::<TaskwarriorTask>(content, &mut parsed)?,
+        ImportFormat::Ics => {
+            for (line, result, warnings) in parse_vtodos(content) {
+                let location = format!("line {}", line);
+                parsed
+                    .warnings
+                    .extend(warnings.into_iter().map(|message| ImportError {
+                        location: location.clone(),
+                        message,
+                    }));
+                parsed.push(location, result);
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+/// Reads a JSON array of objects, or one object per line
+fn parse_json<T>(content: &str, parsed: &mut ParsedImport) -> anyhow::Result<()>
+where
+    T: DeserializeOwned,
+    NewTask: TryFrom<T, Error = String>,
+{
+    if content.trim_start().starts_with('[') {
+        let values: Vec<serde_json::Value> =
+            serde_json::from_str(content).context("Invalid JSON array of tasks")?;
+        for (i, value) in values.into_iter().enumerate() {
+            let result = serde_json::from_value::<T>(value)
+                .map_err(|e| e.to_string())
+                .and_then(NewTask::try_from);
+            parsed.push(format!("task {}", i + 1), result);
+        }
+        return Ok(());
+    }
+    for (i, line) in content.lines().enumerate() {
+        if !line.trim().is_empty() {
+            let result = serde_json::from_str::<T>(line)
+                .map_err(|e| e.to_string())
+                .and_then(NewTask::try_from);
+            parsed.push(format!("line {}", i + 1), result);
+        }
+    }
+    Ok(())
+}
+
+/// Fields of a CSV row or JSON object, named as in the `--output` formats
+#[derive(Deserialize, Debug, Default)]
+struct ImportedTask {
+    description: Option<String>,
+    scope: Option<String>,
+    created_at: Option<String>,
+    completed_at: Option<String>,
+    due_at: Option<String>,
+}
+
+impl TryFrom<ImportedTask> for NewTask {
+    type Error = String;
+
+    fn try_from(task: ImportedTask) -> Result<Self, Self::Error> {
+        let description = non_empty(task.description).ok_or("missing description")?;
+        Ok(NewTask {
+            description,
+            scope: non_empty(task.scope).map(Scope::new),
+            due_at: parse_field("due_at", task.due_at)?,
+            created_at: parse_field("created_at", task.created_at)?,
+            completed_at: parse_field("completed_at", task.completed_at)?,
+            ..Default::default()
+        })
+    }
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_field(name: &str, value: Option<String>) -> Result<Option<DateTime<Local>>, String> {
+    non_empty(value)
+        .map(|value| parse_datetime(&value).ok_or(format!("invalid {} '{}'", name, value)))
+        .transpose()
+}
+
+/// Reads a line such as `x 2026-10-19 2026-10-12 Deploy the API +ops due:2026-10-20`.
+///
+/// The first project becomes the scope and the priority is dropped. Completed tasks without
+/// a completion date are completed at the time of the import.
+pub fn parse_todotxt_line(line: &str) -> Result<NewTask, String> {
+    let mut words = line.split_whitespace().peekable();
+    let mut task = NewTask::default();
+    if words.next_if_eq(&"x").is_some() {
+        task.completed_at = match words.peek().and_then(|word| parse_todotxt_date(word)) {
+            Some(date) => {
+                words.next();
+                Some(date)
+            }
+            None => Some(Local::now()),
+        };
+    }
+    words.next_if(|word| is_priority(word));
+    if let Some(date) = words.peek().and_then(|word| parse_todotxt_date(word)) {
+        words.next();
+        task.created_at = Some(date);
+    }
+    let mut description = vec![];
+    for word in words {
+        match (word.strip_prefix('+'), word.strip_prefix("due:")) {
+            (Some(project), _) if task.scope.is_none() && !project.is_empty() => {
+                task.scope = Some(Scope::new(project.to_string()))
+            }
+            (_, Some(due)) => {
+                let due_at =
+                    parse_todotxt_date(due).ok_or(format!("invalid due date '{}'", due))?;
+                task.due_at = Some(due_at);
+            }
+            _ => description.push(word),
+        }
+    }
+    if description.is_empty() {
+        return Err("missing description".to_string());
+    }
+    task.description = description.join(" ");
+    Ok(task)
+}
+
+fn parse_todotxt_date(word: &str) -> Option<DateTime<Local>> {
+    NaiveDate::parse_from_str(word, "%Y-%m-%d")
+        .ok()
+        .map(start_of_day)
+}
+
+/// Whether a word is a todo.txt priority such as `(A)`
+fn is_priority(word: &str) -> bool {
+    matches!(word.as_bytes(), [b'(', b'A'..=b'Z', b')'])
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Local};
+
+    use super::{parse_import, parse_todotxt_line, ImportFormat};
+
+    fn day(date: Option<DateTime<Local>>) -> Option<String> {
+        date.map(|date| date.format("%Y-%m-%d").to_string())
+    }
+
+    #[test]
+    fn todotxt_lines_keep_their_dates() {
+        let task =
+            parse_todotxt_line("x 2026-10-19 2026-10-12 Deploy the API +ops +infra due:2026-10-20")
+                .unwrap();
+        assert_eq!(task.description, "Deploy the API +infra");
+        assert_eq!(task.scope.unwrap().as_ref(), "ops");
+        assert_eq!(day(task.completed_at).unwrap(), "2026-10-19");
+        assert_eq!(day(task.created_at).unwrap(), "2026-10-12");
+        assert_eq!(day(task.due_at).unwrap(), "2026-10-20");
+        let task = parse_todotxt_line("(A) 2026-10-12 Call mom @phone").unwrap();
+        assert_eq!(task.description, "Call mom @phone");
+        assert_eq!(day(task.created_at).unwrap(), "2026-10-12");
+        assert!(task.completed_at.is_none());
+    }
+
+    #[test]
+    fn invalid_lines_are_reported_without_stopping() {
+        let content = "Water plants\n\n+home due:someday\nx 2026-10-19 Pay rent\n";
+        let parsed = parse_import(content, ImportFormat::Todotxt).unwrap();
+        assert_eq!(parsed.tasks.len(), 2);
+        let errors: Vec<String> = parsed.errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(errors, ["line 3: invalid due date 'someday'"]);
+    }
+
+    #[test]
+    fn csv_and_json_fields_are_read_by_name() {
+        let csv = "id,description,scope,created_at,completed_at\n\
+            4,Deploy,ops,2026-10-12T09:00:00+00:00,\n\
+            5,,ops,,\n\
+            6,Review,,2026-10-13 10:00:00,yesterday\n";
+        let parsed = parse_import(csv, ImportFormat::Csv).unwrap();
+        assert_eq!(parsed.tasks.len(), 1);
+        assert_eq!(parsed.tasks[0].scope.as_ref().unwrap().as_ref(), "ops");
+        let errors: Vec<String> = parsed.errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            errors,
+            [
+                "line 3: missing description",
+                "line 4: invalid completed_at 'yesterday'"
+            ]
+        );
+        let json = r#"[{"description": "Deploy", "due_at": "2026-10-20"}, {"scope": "ops"}]"#;
+        let parsed = parse_import(json, ImportFormat::Json).unwrap();
+        assert_eq!(day(parsed.tasks[0].due_at).unwrap(), "2026-10-20");
+        assert_eq!(parsed.errors[0].to_string(), "task 2: missing description");
+        let jsonl = "{\"description\": \"Deploy\"}\n{\"description\": 4}\n";
+        let parsed = parse_import(jsonl, ImportFormat::Json).unwrap();
+        assert_eq!(parsed.tasks.len(), 1);
+        assert_eq!(parsed.errors[0].location, "line 2");
+        assert!(parse_import("[{", ImportFormat::Json).is_err());
+    }
+}
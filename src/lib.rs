@@ -1,9 +1,24 @@
+pub mod agenda;
 pub mod cli;
 pub mod configuration;
+pub mod dates;
 pub mod domain;
+pub mod export;
+pub mod filter;
 pub mod generator;
+pub mod grouping;
+pub mod icalendar;
+pub mod import;
+pub mod output;
+pub mod pager;
 pub mod scopes;
+pub mod shell;
 pub mod startup;
 pub mod storage;
 pub mod tabular;
 pub mod tasks;
+pub mod taskwarrior;
+pub mod template;
+pub mod theme;
+pub mod tui;
+pub mod views;
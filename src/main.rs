@@ -1,13 +1,38 @@
+use std::{
+    fs::{self, File},
+    io::{self, stdin, stdout, BufWriter, Write},
+    iter,
+};
+
+use anyhow::{anyhow, bail, Context};
+use chrono::Local;
 use clap::Parser;
 use tasks::{
-    cli::{Cli, Commands, ScopeCommands},
+    agenda::{agenda, count_month, start_of_month},
+    cli::{Cli, Commands, ListArgs, ScopeCommands, ViewCommands},
     configuration::Settings,
-    domain::{NewTask, Scope, Task},
+    dates::start_of_day,
+    domain::{NewTask, Scope, Task, TaskStatus, View},
+    export::{todotxt_line, write_export, ExportFormat},
+    filter::parse_filter,
+    generator::Generator,
+    grouping::{board_lanes, group_tasks, GroupedTasks},
+    import::parse_import,
+    output::{print_record, print_records, OutputFormat},
+    pager::{ignore_broken_pipe, print_paged},
     scopes,
+    shell::{parse_line, Completions, Shell, ShellLine},
     startup::{ensure_initialized, Application},
     storage::{self, Folder},
-    tabular::get_tasks_table,
-    tasks::{add_task, complete_task, delete_task, list_tasks},
+    tabular::{
+        get_task_board, get_tasks_table, paint, ConsoleCalendar, ConsoleTable, TaskTableOptions,
+    },
+    tasks::{
+        add_task, assign_uuids, complete_task, delete_task, get_task_by_id, get_taskwarrior_data,
+        import_tasks, list_tasks, resolve_task_ref, search_tasks, search_terms, ListQuery,
+    },
+    template::Template,
+    tui, views,
 };
 use terminal_size::terminal_size;
 
@@ -20,55 +45,553 @@ async fn main() -> anyhow::Result<()> {
         storage_folder.set_extension("db");
         storage_folder
     };
-    let app_settings = Settings::new(storage_folder);
+    let config_file = storage::get_folder_path(Folder::LocalSettings).join("config.toml");
+    let mut app_settings = Settings::new(storage_folder).with_config_file(&config_file)?;
+    apply_overrides(&mut app_settings, &args);
 
     ensure_initialized(&app_settings).await?;
     let app = Application::build(app_settings).await?;
     match args.get_command() {
-        Commands::Add { description, scope } => {
+        Commands::Shell => shell(app).await,
+        command => run(&app, command, args.get_output()).await,
+    }
+}
+
+/// Runs one command, from the command line or the shell
+async fn run(app: &Application, command: Commands, output: OutputFormat) -> anyhow::Result<()> {
+    match command {
+        Commands::Add {
+            description,
+            scope,
+            due,
+        } => {
             let input = NewTask {
                 description,
                 scope: scope.map(Scope::new),
+                due_at: due.map(start_of_day),
+                ..Default::default()
             };
-            add_task(&app.pool, &app.generator, input).await?;
+            let task = add_task(&app.pool, input).await?;
+            if output != OutputFormat::Table {
+                print_record(output, &task)?;
+            }
+        }
+        Commands::List(args) => list(app, args, output).await?,
+        Commands::Tui => tui::run(app).await?,
+        Commands::Shell => bail!("Already in a shell"),
+        Commands::Board { scope, by } => {
+            let tasks = list_scope_tasks(app, scope).await?;
+            let grouped = board_lanes(tasks, by);
+            if output != OutputFormat::Table {
+                return print_grouped_records(grouped, output);
+            }
+            let scopes = scopes::get_details(&app.pool).await?;
+            let width = terminal_size().map_or(120, |(w, _)| w.0);
+            let board = get_task_board(width, scopes, &app.settings.theme)
+                .set_colored(app.settings.color.enabled());
+            let lanes = grouped
+                .groups
+                .into_iter()
+                .map(|group| (format!("{} ({})", group.name, group.count), group.tasks));
+            let text = board.to_string(lanes);
+            print_paged(&text, board.header_height(), app.settings.pager)?;
         }
-        Commands::List { scope } => {
-            let tasks = list_tasks(&app.pool, scope.map(Scope::new)).await?;
+        Commands::Agenda { days, by, scope } => {
+            let tasks = list_scope_tasks(app, scope).await?;
+            let grouped = agenda(tasks, by, Local::now().date_naive(), days);
+            let options = TaskTableOptions {
+                columns: app.settings.list.columns.clone(),
+                ..Default::default()
+            };
+            print_grouped_tasks(app, grouped, None, &options, output).await?;
+        }
+        Commands::Calendar { month, by, scope } => {
+            let today = Local::now().date_naive();
+            let month = month.unwrap_or_else(|| start_of_month(today));
+            let tasks = list_scope_tasks(app, scope).await?;
+            let days = count_month(&tasks, by, month);
+            if output != OutputFormat::Table {
+                return print_records(output, &days);
+            }
+            let calendar = ConsoleCalendar::default()
+                .set_border_style(app.settings.theme.border)
+                .set_header_style(app.settings.theme.header)
+                .set_colored(app.settings.color.enabled())
+                .set_today(today);
+            let text = calendar.to_string(&days);
+            print_paged(&text, calendar.header_height(), app.settings.pager)?;
+        }
+        Commands::Export {
+            format,
+            file,
+            scope,
+            filter,
+        } => {
+            let query = ListQuery {
+                scope: scope.map(Scope::new),
+                filter: filter.as_deref().map(parse_filter).transpose()?,
+                ..Default::default()
+            };
+            let tasks = list_tasks(&app.pool, &query).await?;
             let tasks: Vec<Task> = tasks.into_iter().filter_map(|x| x.ok()).collect();
-            let builder = terminal_size()
-                .map(|(w, _)| w.0)
-                .or(Some(120))
-                .map(get_tasks_table)
-                .unwrap();
-            let table = builder.build().unwrap();
-            table.print(tasks);
+            let mut writer: Box<dyn Write> = match file {
+                Some(path) => {
+                    let file = File::create(&path)
+                        .with_context(|| format!("Failed to create {}", path.display()))?;
+                    Box::new(BufWriter::new(file))
+                }
+                None => Box::new(stdout().lock()),
+            };
+            if matches!(format, ExportFormat::Taskwarrior | ExportFormat::Ics) {
+                let ids: Vec<u32> = tasks.iter().map(|task| task.id.clone().into()).collect();
+                assign_uuids(&app.pool, &ids).await?;
+            }
+            let data = get_taskwarrior_data(&app.pool).await?;
+            let result = write_export(&mut writer, format, tasks, &data, Local::now())
+                .and_then(|()| writer.flush());
+            ignore_broken_pipe(result)?;
+        }
+        Commands::Import {
+            file,
+            format,
+            dry_run,
+        } => {
+            let content = match file.to_str() {
+                Some("-") => io::read_to_string(stdin())?,
+                _ => fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read {}", file.display()))?,
+            };
+            let parsed = parse_import(&content, format)?;
+            for warning in &parsed.warnings {
+                eprintln!("Warning: {}", warning);
+            }
+            for error in &parsed.errors {
+                eprintln!("Skipped {}", error);
+            }
+            if dry_run {
+                if output != OutputFormat::Table {
+                    return print_records(output, &parsed.tasks);
+                }
+                println!("Would import {} tasks:", parsed.tasks.len());
+                // Ids are only given when the tasks are saved
+                let generator = Generator::default();
+                for input in parsed.tasks {
+                    println!("  {}", todotxt_line(&Task::new(input, &generator)));
+                }
+                return Ok(());
+            }
+            let imported = import_tasks(&app.pool, parsed.tasks).await?;
+            let (added, updated) = (imported.added.len(), imported.updated.len());
+            match output {
+                OutputFormat::Table if updated > 0 => println!(
+                    "Successfully imported {} tasks and updated {} existing ones",
+                    added, updated
+                ),
+                OutputFormat::Table => println!("Successfully imported {} tasks", added),
+                _ => {
+                    let tasks: Vec<&Task> =
+                        imported.added.iter().chain(&imported.updated).collect();
+                    print_records(output, &tasks)?
+                }
+            }
+        }
+        Commands::Search { query, scope } => {
+            let tasks = search_tasks(&app.pool, &query, scope.map(Scope::new)).await?;
+            let options = TaskTableOptions {
+                highlighted_terms: search_terms(&query),
+                ..Default::default()
+            };
+            print_tasks(app, tasks, &options, output).await?;
+        }
+        Commands::Show { id, format } => {
+            let template = parse_template(format.as_deref(), output)?;
+            let task = match resolve_task_ref(&app.pool, &id).await? {
+                Some(task_id) => get_task_by_id(&app.pool, task_id).await?,
+                None => None,
+            };
+            match task {
+                Some(task) => match template {
+                    Some(template) => println!("{}", template.render(&task)),
+                    None if output == OutputFormat::Table => print_task(&task),
+                    None => print_record(output, &task)?,
+                },
+                None => not_found(output, format!("Task with id {} not found", id))?,
+            }
         }
         Commands::Complete { id } => {
-            let success = complete_task(&app.pool, id).await?;
-            if success {
-                println!("Successfully completed task with id {}", id)
-            } else {
-                println!("Task with id {} not found", id)
+            let task = match resolve_task_ref(&app.pool, &id).await? {
+                Some(task_id) => complete_task(&app.pool, task_id).await?,
+                None => None,
+            };
+            match task {
+                Some(_) if output == OutputFormat::Table => {
+                    println!("Successfully completed task with id {}", id)
+                }
+                Some(task) => print_record(output, &task)?,
+                None => not_found(output, format!("Task with id {} not found", id))?,
             }
         }
         Commands::Delete { id } => {
-            let success = delete_task(&app.pool, id).await?;
-            if success {
-                println!("Successfully deleted task with id {}", id)
-            } else {
-                println!("Task with id {} not found", id)
+            let task = match resolve_task_ref(&app.pool, &id).await? {
+                Some(task_id) => delete_task(&app.pool, task_id).await?,
+                None => None,
+            };
+            match task {
+                Some(_) if output == OutputFormat::Table => {
+                    println!("Successfully deleted task with id {}", id)
+                }
+                Some(task) => print_record(output, &task)?,
+                None => not_found(output, format!("Task with id {} not found", id))?,
             }
         }
+        Commands::View {
+            name: Some(name),
+            action: None,
+        } => {
+            let args = ListArgs {
+                view: Some(name),
+                ..Default::default()
+            };
+            list(app, args, output).await?
+        }
+        Commands::View { action, .. } => match action.unwrap_or(ViewCommands::List) {
+            ViewCommands::Save { name, arguments } => {
+                validate_view_arguments(&arguments)?;
+                let view = View::new(name, arguments);
+                views::save(&app.pool, &view).await?;
+                match output {
+                    OutputFormat::Table => println!("Successfully saved view {}", view.name),
+                    _ => print_record(output, &view)?,
+                }
+            }
+            ViewCommands::Edit { name, arguments } => {
+                validate_view_arguments(&arguments)?;
+                let view = View::new(name, arguments);
+                match views::update(&app.pool, &view).await? {
+                    true if output == OutputFormat::Table => {
+                        println!("Successfully updated view {}", view.name)
+                    }
+                    true => print_record(output, &view)?,
+                    false => not_found(output, format!("View {} not found", view.name))?,
+                }
+            }
+            ViewCommands::List => {
+                let views = views::list(&app.pool).await?;
+                if output != OutputFormat::Table {
+                    return print_records(output, &views);
+                }
+                println!("The following views have been found:");
+                for view in views {
+                    println!("{}: {}", view.name, view.command_line())
+                }
+            }
+            ViewCommands::Delete { name } => {
+                let view = views::get(&app.pool, &name).await?;
+                views::delete(&app.pool, &name).await?;
+                match view {
+                    Some(_) if output == OutputFormat::Table => {
+                        println!("Successfully deleted view {}", name)
+                    }
+                    Some(view) => print_record(output, &view)?,
+                    None => not_found(output, format!("View {} not found", name))?,
+                }
+            }
+        },
         Commands::Scope { action } => match action {
             ScopeCommands::List => {
-                let scopes = scopes::list(&app.pool).await?;
+                let scopes = scopes::get_details(&app.pool).await?;
+                if output != OutputFormat::Table {
+                    return print_records(output, &scopes);
+                }
+                let colored = app.settings.color.enabled();
                 println!("The following scopes have been found:");
-                for scope in scopes {
-                    println!("{}", scope.as_ref())
+                for details in scopes {
+                    let name = match details.key.as_ref() {
+                        Some(key) => format!("{} [{}]", details.display_name(), key),
+                        None => details.display_name(),
+                    };
+                    match details.color {
+                        Some(color) if colored => println!("{}", paint(&name, color)),
+                        Some(color) => println!("{} ({})", name, color),
+                        None => println!("{}", name),
+                    }
                 }
             }
+            ScopeCommands::SetColor { scope, color } => {
+                let scope = Scope::new(scope);
+                scopes::set_color(&app.pool, &scope, color).await?;
+                print_scope_details(app, &scope, output).await?;
+            }
+            ScopeCommands::SetLabel { scope, label } => {
+                let scope = Scope::new(scope);
+                scopes::set_label(&app.pool, &scope, label).await?;
+                print_scope_details(app, &scope, output).await?;
+            }
+            ScopeCommands::SetKey { scope, key } => {
+                let scope = Scope::new(scope);
+                scopes::set_key(&app.pool, &scope, key).await?;
+                print_scope_details(app, &scope, output).await?;
+            }
         },
     }
 
     Ok(())
 }
+
+async fn shell(mut app: Application) -> anyhow::Result<()> {
+    let settings = app.settings.clone();
+    let history = storage::get_folder_path(Folder::Local).join("shell_history");
+    let mut shell = Shell::new(history)?;
+    let mut scope: Option<Scope> = None;
+    loop {
+        shell.set_completions(Completions::load(&app.pool).await?);
+        let Some(line) = shell.read_line(scope.as_ref())? else {
+            break;
+        };
+        let words = match parse_line(&line) {
+            Ok(ShellLine::Empty) => continue,
+            Ok(ShellLine::Exit) => break,
+            Ok(ShellLine::Use(name)) => {
+                scope = name;
+                continue;
+            }
+            Ok(ShellLine::Command(words)) => words,
+            Err(e) => {
+                eprintln!("{}", e);
+                continue;
+            }
+        };
+        let args = match Cli::try_parse_from(iter::once("tasks-cli".to_string()).chain(words)) {
+            Ok(args) => args,
+            Err(e) => {
+                e.print()?;
+                continue;
+            }
+        };
+        app.settings = settings.clone();
+        apply_overrides(&mut app.settings, &args);
+        let command = match scope.as_ref() {
+            Some(scope) => args.get_command().with_default_scope(scope),
+            None => args.get_command(),
+        };
+        if let Err(e) = run(&app, command, args.get_output()).await {
+            eprintln!("Error: {:#}", e);
+        }
+    }
+    shell.save_history()
+}
+
+/// Applies the global options that override the configuration
+fn apply_overrides(settings: &mut Settings, args: &Cli) {
+    if let Some(color) = args.get_color() {
+        settings.color = color;
+    }
+    if args.get_no_pager() {
+        settings.pager = false;
+    }
+}
+
+async fn list(app: &Application, args: ListArgs, output: OutputFormat) -> anyhow::Result<()> {
+    let args = match args.view.as_ref() {
+        Some(name) => {
+            let view = views::get(&app.pool, name)
+                .await?
+                .ok_or_else(|| anyhow!("View {} not found", name))?;
+            let view_args = ListArgs::from_view_arguments(&view.arguments)
+                .with_context(|| format!("Invalid arguments in view {}", name))?;
+            args.merge(view_args)
+        }
+        None => args,
+    };
+    let template = parse_template(args.format.as_deref(), output)?;
+    let now = Local::now();
+    let status = match (args.open, args.done) {
+        (true, _) => Some(TaskStatus::Open),
+        (_, true) => Some(TaskStatus::Done),
+        _ => None,
+    };
+    let query = ListQuery {
+        scope: args.scope.map(Scope::new),
+        filter: args.filter.as_deref().map(parse_filter).transpose()?,
+        status,
+        created_since: args.created_since.and_then(|d| d.resolve(now)),
+        created_before: args.created_before.and_then(|d| d.resolve(now)),
+        completed_since: args.completed_since.and_then(|d| d.resolve(now)),
+        completed_before: args.completed_before.and_then(|d| d.resolve(now)),
+        sort: args.sort,
+        reverse: args.reverse,
+        limit: args.limit,
+        offset: args.offset,
+    };
+    let tasks = list_tasks(&app.pool, &query).await?;
+    let columns = match args.columns.is_empty() {
+        true => app.settings.list.columns.clone(),
+        false => args.columns,
+    };
+    let options = TaskTableOptions {
+        columns,
+        color_rows: args.color_rows,
+        wrap: args.wrap,
+        ..Default::default()
+    };
+    if let Some(group_by) = args.group_by {
+        let tasks = tasks.into_iter().filter_map(|x| x.ok()).collect();
+        let grouped = group_tasks(tasks, group_by);
+        return print_grouped_tasks(app, grouped, template, &options, output).await;
+    }
+    if let Some(template) = template {
+        let text: String = tasks
+            .into_iter()
+            .filter_map(|x| x.ok())
+            .map(|task| format!("{}\n", template.render(&task)))
+            .collect();
+        print_paged(&text, 0, app.settings.pager)?;
+        return Ok(());
+    }
+    print_tasks(app, tasks, &options, output).await?;
+    Ok(())
+}
+
+fn parse_template(
+    template: Option<&str>,
+    output: OutputFormat,
+) -> anyhow::Result<Option<Template>> {
+    if template.is_some() && output != OutputFormat::Table {
+        bail!("--format cannot be combined with --output");
+    }
+    Ok(template.map(Template::parse).transpose()?)
+}
+
+fn validate_view_arguments(arguments: &[String]) -> anyhow::Result<()> {
+    let args = ListArgs::from_view_arguments(arguments).context("Invalid view arguments")?;
+    if args.view.is_some() {
+        bail!("Views cannot reference other views");
+    }
+    Ok(())
+}
+
+async fn print_tasks(
+    app: &Application,
+    tasks: Vec<anyhow::Result<Task>>,
+    options: &TaskTableOptions,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let tasks: Vec<Task> = tasks.into_iter().filter_map(|x| x.ok()).collect();
+    if output != OutputFormat::Table {
+        return print_records(output, &tasks);
+    }
+    let table = build_tasks_table(app, options).await?;
+    let text = table.to_string(tasks);
+    print_paged(&text, table.header_height(), app.settings.pager)?;
+    Ok(())
+}
+
+async fn print_grouped_tasks(
+    app: &Application,
+    grouped: GroupedTasks,
+    template: Option<Template>,
+    options: &TaskTableOptions,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    if output != OutputFormat::Table {
+        return print_grouped_records(grouped, output);
+    }
+    let groups = grouped
+        .groups
+        .into_iter()
+        .map(|group| (format!("{} ({})", group.name, group.count), group.tasks));
+    match template {
+        Some(template) => {
+            let mut text = String::new();
+            for (title, tasks) in groups {
+                text.push_str(&format!("{}\n", title));
+                for task in tasks {
+                    text.push_str(&format!("{}\n", template.render(&task)));
+                }
+            }
+            print_paged(&text, 0, app.settings.pager)?;
+        }
+        None => {
+            let table = build_tasks_table(app, options).await?;
+            let mut text = table.to_grouped_string(groups);
+            text.push_str(&format!("Total: {}\n", grouped.total));
+            print_paged(&text, table.header_height(), app.settings.pager)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_grouped_records(grouped: GroupedTasks, output: OutputFormat) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            bail!("Grouped tasks cannot be written as csv or tsv")
+        }
+        OutputFormat::Jsonl => print_records(output, &grouped.groups),
+        _ => print_record(output, &grouped),
+    }
+}
+
+/// Lists all the tasks of a scope, or of every scope
+async fn list_scope_tasks(app: &Application, scope: Option<String>) -> anyhow::Result<Vec<Task>> {
+    let query = ListQuery {
+        scope: scope.map(Scope::new),
+        ..Default::default()
+    };
+    let tasks = list_tasks(&app.pool, &query).await?;
+    Ok(tasks.into_iter().filter_map(|x| x.ok()).collect())
+}
+
+async fn build_tasks_table(
+    app: &Application,
+    options: &TaskTableOptions,
+) -> anyhow::Result<ConsoleTable<Task>> {
+    let scopes = scopes::get_details(&app.pool).await?;
+    let width = terminal_size().map_or(120, |(w, _)| w.0);
+    let builder = get_tasks_table(width, scopes, options, &app.settings.theme)
+        .set_colored(app.settings.color.enabled());
+    builder.build().context("Failed laying out the task table")
+}
+
+/// Prints the details of a scope after a change, for machine-readable outputs only
+async fn print_scope_details(
+    app: &Application,
+    scope: &Scope,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    if output == OutputFormat::Table {
+        return Ok(());
+    }
+    let details = scopes::get_details(&app.pool)
+        .await?
+        .into_iter()
+        .find(|details| &details.scope == scope);
+    print_record(output, &details)
+}
+
+/// Reports a missing item, failing for machine-readable outputs
+fn not_found(output: OutputFormat, message: String) -> anyhow::Result<()> {
+    if output != OutputFormat::Table {
+        bail!(message);
+    }
+    println!("{}", message);
+    Ok(())
+}
+
+fn print_task(task: &Task) {
+    println!("ID:          {}", task.id);
+    if let Some(key) = task.key.as_ref() {
+        println!("Key:         {}", key);
+    }
+    println!("Description: {}", task.description);
+    println!(
+        "Scope:       {}",
+        task.scope.as_ref().map_or("None", |s| s.as_ref())
+    );
+    println!(
+        "Created at:  {}",
+        task.created_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    if let Some(completed_at) = task.completed_at {
+        println!("Completed:   {}", completed_at.format("%Y-%m-%d %H:%M:%S"));
+    }
+}
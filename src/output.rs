@@ -0,0 +1,147 @@
+use std::io::{stdout, Write};
+
+use anyhow::Context;
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutputFormat {
+    /// Human readable text and tables
+    #[default]
+    Table,
+    /// A JSON document
+    Json,
+    /// One JSON document per line
+    Jsonl,
+    /// Comma separated values with a header row
+    Csv,
+    /// Tab separated values with a header row
+    Tsv,
+    /// A YAML document
+    Yaml,
+}
+
+/// Prints a collection of records in a machine-readable format
+pub fn print_records<T: Serialize>(format: OutputFormat, records: &[T]) -> anyhow::Result<()> {
+    let mut writer = stdout().lock();
+    write_records(&mut writer, format, records)?;
+    writer.flush().context("Failed to write output")
+}
+
+/// Prints a single record in a machine-readable format
+pub fn print_record<T: Serialize>(format: OutputFormat, record: &T) -> anyhow::Result<()> {
+    let mut writer = stdout().lock();
+    write_record(&mut writer, format, record)?;
+    writer.flush().context("Failed to write output")
+}
+
+pub fn write_records<W: Write, T: Serialize>(
+    writer: &mut W,
+    format: OutputFormat,
+    records: &[T],
+) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, records)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Jsonl => {
+            for record in records {
+                serde_json::to_writer(&mut *writer, record)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Csv => write_delimited(writer, b',', records)?,
+        OutputFormat::Tsv => write_delimited(writer, b'\t', records)?,
+        OutputFormat::Yaml => serde_norway::to_writer(writer, records)?,
+        OutputFormat::Table => unreachable!("tables are rendered by the caller"),
+    }
+    Ok(())
+}
+
+pub fn write_record<W: Write, T: Serialize>(
+    writer: &mut W,
+    format: OutputFormat,
+    record: &T,
+) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, record)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Yaml => serde_norway::to_writer(writer, record)?,
+        _ => write_records(writer, format, std::slice::from_ref(record))?,
+    }
+    Ok(())
+}
+
+fn write_delimited<W: Write, T: Serialize>(
+    writer: &mut W,
+    delimiter: u8,
+    records: &[T],
+) -> anyhow::Result<()> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(writer);
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::{write_record, write_records, OutputFormat};
+
+    #[derive(Serialize)]
+    struct Record {
+        id: u32,
+        scope: Option<String>,
+    }
+
+    fn render(format: OutputFormat, records: &[Record]) -> String {
+        let mut buffer = vec![];
+        write_records(&mut buffer, format, records).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                id: 1,
+                scope: Some("work".to_string()),
+            },
+            Record { id: 2, scope: None },
+        ]
+    }
+
+    #[test]
+    fn records_are_rendered_in_each_format() {
+        assert_eq!(
+            "{\"id\":1,\"scope\":\"work\"}\n{\"id\":2,\"scope\":null}\n",
+            render(OutputFormat::Jsonl, &records())
+        );
+        assert_eq!(
+            "id,scope\n1,work\n2,\n",
+            render(OutputFormat::Csv, &records())
+        );
+        assert_eq!(
+            "id\tscope\n1\twork\n2\t\n",
+            render(OutputFormat::Tsv, &records())
+        );
+        assert_eq!(
+            "- id: 1\n  scope: work\n- id: 2\n  scope: null\n",
+            render(OutputFormat::Yaml, &records())
+        );
+    }
+
+    #[test]
+    fn single_json_record_is_an_object() {
+        let mut buffer = vec![];
+        write_record(&mut buffer, OutputFormat::Json, &records()[0]).unwrap();
+        assert!(String::from_utf8(buffer).unwrap().starts_with('{'));
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    env,
+    io::{self, stdout, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use terminal_size::{terminal_size, Height};
+
+const DEFAULT_PAGER: &str = "less -R";
+
+/// First version of less with the `--header` option
+const LESS_HEADER_VERSION: u32 = 600;
+
+/// Prints `text`, through `$PAGER` when it is taller than the terminal.
+///
+/// The first `header_lines` lines stay visible while scrolling when the pager is a recent
+/// enough `less`. Text is printed directly when the pager cannot be started.
+pub fn print_paged(text: &str, header_lines: usize, enabled: bool) -> io::Result<()> {
+    if enabled && exceeds_terminal(text) {
+        if let Some(result) = page(text, header_lines) {
+            return result;
+        }
+    }
+    ignore_broken_pipe(stdout().lock().write_all(text.as_bytes()))
+}
+
+fn exceeds_terminal(text: &str) -> bool {
+    stdout().is_terminal()
+        && terminal_size().is_some_and(|(_, Height(height))| {
+            // Leaves room for the prompt shown after the output
+            text.lines().count() >= usize::from(height)
+        })
+}
+
+fn page(text: &str, header_lines: usize) -> Option<io::Result<()>> {
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let mut words = pager.split_whitespace();
+    let program = words.next()?;
+    let mut command = Command::new(program);
+    command.args(words).stdin(Stdio::piped());
+    if is_less(program) {
+        if env::var_os("LESS").is_none() {
+            command.env("LESS", "R");
+        }
+        if header_lines > 0 && get_less_version(program).unwrap_or(0) >= LESS_HEADER_VERSION {
+            command.arg(format!("--header={}", header_lines));
+        }
+    }
+    let mut child = command.spawn().ok()?;
+    let written = match child.stdin.take() {
+        Some(mut stdin) => ignore_broken_pipe(stdin.write_all(text.as_bytes())),
+        None => Ok(()),
+    };
+    Some(written.and_then(|_| child.wait().map(|_| ())))
+}
+
+fn is_less(program: &str) -> bool {
+    program == "less" || program.ends_with("/less")
+}
+
+fn get_less_version(program: &str) -> Option<u32> {
+    let output = Command::new(program).arg("--version").output().ok()?;
+    parse_less_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Reads the version from the output of `less --version`, e.g. `less 643 (PCRE2 ...)`
+fn parse_less_version(output: &str) -> Option<u32> {
+    let version = output.lines().next()?.strip_prefix("less ")?;
+    let end = version
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(version.len());
+    version[..end].parse().ok()
+}
+
+/// Stops silently when the reader, such as a pager or `head`, quits early
+pub fn ignore_broken_pipe(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_less_version;
+
+    #[test]
+    fn less_versions_are_parsed() {
+        assert_eq!(
+            parse_less_version("less 643 (PCRE2 regular expressions)\nCopyright"),
+            Some(643)
+        );
+        assert_eq!(
+            parse_less_version("less 590x (GNU regular expressions)"),
+            Some(590)
+        );
+        assert_eq!(parse_less_version("most 5.1"), None);
+    }
+}
@@ -1,3 +1,5 @@
+mod details;
 mod list;
 
+pub use details::{get_details, get_key, set_color, set_key, set_label};
 pub use list::list;
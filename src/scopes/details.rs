@@ -0,0 +1,94 @@
+use anyhow::Context;
+use sqlx::SqlitePool;
+
+use crate::domain::{Color, Scope, ScopeDetails};
+
+pub async fn get_details(pool: &SqlitePool) -> anyhow::Result<Vec<ScopeDetails>> {
+    let details = sqlx::query!(
+        r#"
+            SELECT name as "name!", color, label, key
+            FROM scopes
+            WHERE color IS NOT NULL OR label IS NOT NULL OR key IS NOT NULL
+                OR name IN (SELECT scope FROM tasks)
+            UNION
+            SELECT DISTINCT scope as "name!", NULL, NULL, NULL
+            FROM tasks
+            WHERE scope IS NOT NULL AND scope NOT IN (SELECT name FROM scopes)
+            ORDER BY 1
+        "#
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|r| ScopeDetails {
+        scope: Scope::new(r.name),
+        color: r.color.and_then(|c| c.parse().ok()),
+        label: r.label,
+        key: r.key,
+    })
+    .collect();
+    Ok(details)
+}
+
+pub async fn set_color(
+    pool: &SqlitePool,
+    scope: &Scope,
+    color: Option<Color>,
+) -> anyhow::Result<()> {
+    let color = color.map(|c| c.to_string());
+    sqlx::query!(
+        r#"
+            INSERT INTO scopes (name, color)
+            VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE SET color = excluded.color
+        "#,
+        scope,
+        color
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn set_label(
+    pool: &SqlitePool,
+    scope: &Scope,
+    label: Option<String>,
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO scopes (name, label)
+            VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE SET label = excluded.label
+        "#,
+        scope,
+        label
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn get_key(pool: &SqlitePool, scope: &Scope) -> anyhow::Result<Option<String>> {
+    let key = sqlx::query!(r#"SELECT key FROM scopes WHERE name = $1"#, scope)
+        .fetch_optional(pool)
+        .await?
+        .and_then(|r| r.key);
+    Ok(key)
+}
+
+pub async fn set_key(pool: &SqlitePool, scope: &Scope, key: Option<String>) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO scopes (name, key)
+            VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE SET key = excluded.key
+        "#,
+        scope,
+        key
+    )
+    .execute(pool)
+    .await
+    .context("Failed to set key, is it already used by another scope?")?;
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use std::path::PathBuf;
+
+use clap::CommandFactory;
+use rustyline::{
+    completion::{Completer, Pair},
+    error::ReadlineError,
+    highlight::Highlighter,
+    hint::Hinter,
+    history::DefaultHistory,
+    validate::Validator,
+    CompletionType, Config, Context, Editor, Helper,
+};
+use sqlx::SqlitePool;
+
+use crate::{
+    cli::Cli,
+    domain::Scope,
+    scopes,
+    tasks::{list_tasks, ListQuery},
+};
+
+/// Options followed by a scope name, completed with the existing scopes
+const SCOPE_OPTIONS: [&str; 3] = ["-s", "--scope", "use"];
+
+/// Line typed in the shell
+#[derive(Debug, PartialEq)]
+pub enum ShellLine {
+    Empty,
+    Exit,
+    /// Sets the scope used by commands without `--scope`, clearing it when omitted
+    Use(Option<Scope>),
+    /// Arguments of a `tasks-cli` command
+    Command(Vec<String>),
+}
+
+/// Splits a line like a POSIX shell would, recognizing the shell builtins
+pub fn parse_line(line: &str) -> Result<ShellLine, String> {
+    let words = shlex::split(line).ok_or("Unterminated quote or trailing escape")?;
+    match words.first().map(String::as_str) {
+        None => Ok(ShellLine::Empty),
+        Some("exit" | "quit") => Ok(ShellLine::Exit),
+        Some("use") => match &words[1..] {
+            [] => Ok(ShellLine::Use(None)),
+            [scope] => Ok(ShellLine::Use(Some(Scope::new(scope.clone())))),
+            _ => Err("Usage: use [SCOPE]".to_string()),
+        },
+        Some(_) => Ok(ShellLine::Command(words)),
+    }
+}
+
+pub fn prompt(scope: Option<&Scope>) -> String {
+    match scope {
+        Some(scope) => format!("tasks ({})> ", scope),
+        None => "tasks> ".to_string(),
+    }
+}
+
+/// Words completed by the tab key
+#[derive(Debug, Default)]
+pub struct Completions {
+    pub ids: Vec<String>,
+    pub scopes: Vec<String>,
+}
+
+impl Completions {
+    /// Reads the ids of all tasks and the names of all scopes
+    pub async fn load(pool: &SqlitePool) -> anyhow::Result<Self> {
+        let ids = list_tasks(pool, &ListQuery::default())
+            .await?
+            .into_iter()
+            .filter_map(|x| x.ok())
+            .map(|task| task.display_id())
+            .collect();
+        let scopes = scopes::list(pool)
+            .await?
+            .into_iter()
+            .map(|scope| scope.to_string())
+            .collect();
+        Ok(Self { ids, scopes })
+    }
+
+    /// Start of the word under the cursor and its possible completions
+    fn complete(&self, line: &str) -> (usize, Vec<String>) {
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let previous: Vec<&str> = line[..start].split_whitespace().collect();
+        let candidates: Vec<String> = match previous.as_slice() {
+            [] => Cli::command()
+                .get_subcommands()
+                .map(|command| command.get_name().to_string())
+                .filter(|name| name != "shell")
+                .chain(["use", "exit"].map(str::to_string))
+                .collect(),
+            ["scope", _] => self.scopes.clone(),
+            [.., option] if SCOPE_OPTIONS.contains(option) => self.scopes.clone(),
+            _ => self.ids.clone(),
+        };
+        let word = &line[start..];
+        let matches = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .collect();
+        (start, matches)
+    }
+}
+
+struct ShellHelper {
+    completions: Completions,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, matches) = self.completions.complete(&line[..pos]);
+        let pairs = matches
+            .into_iter()
+            .map(|word| Pair {
+                display: word.clone(),
+                replacement: word,
+            })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+/// Line editor of the shell, with history saved between sessions
+pub struct Shell {
+    editor: Editor<ShellHelper, DefaultHistory>,
+    history: PathBuf,
+}
+
+impl Shell {
+    pub fn new(history: PathBuf) -> anyhow::Result<Self> {
+        let config = Config::builder()
+            .auto_add_history(true)
+            .completion_type(CompletionType::List)
+            .build();
+        let mut editor = Editor::with_config(config)?;
+        editor.set_helper(Some(ShellHelper {
+            completions: Completions::default(),
+        }));
+        // The history file does not exist before the first session
+        let _ = editor.load_history(&history);
+        Ok(Self { editor, history })
+    }
+
+    pub fn set_completions(&mut self, completions: Completions) {
+        if let Some(helper) = self.editor.helper_mut() {
+            helper.completions = completions;
+        }
+    }
+
+    /// Reads the next line, `None` once the input ends. Ctrl-C discards the current line.
+    pub fn read_line(&mut self, scope: Option<&Scope>) -> anyhow::Result<Option<String>> {
+        match self.editor.readline(&prompt(scope)) {
+            Ok(line) => Ok(Some(line)),
+            Err(ReadlineError::Interrupted) => Ok(Some(String::new())),
+            Err(ReadlineError::Eof) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save_history(&mut self) -> anyhow::Result<()> {
+        self.editor.save_history(&self.history)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::Scope;
+
+    use super::{parse_line, Completions, ShellLine};
+
+    #[test]
+    fn lines_are_split_like_a_shell() {
+        assert_eq!(parse_line("  "), Ok(ShellLine::Empty));
+        assert_eq!(
+            parse_line("add 'Fix login' -s web"),
+            Ok(ShellLine::Command(
+                ["add", "Fix login", "-s", "web"]
+                    .map(str::to_string)
+                    .to_vec()
+            ))
+        );
+        assert_eq!(
+            parse_line("use work"),
+            Ok(ShellLine::Use(Some(Scope::new("work".to_string()))))
+        );
+        assert_eq!(parse_line("use"), Ok(ShellLine::Use(None)));
+        assert!(parse_line("add 'unclosed").is_err());
+    }
+
+    #[test]
+    fn completions_depend_on_the_previous_word() {
+        let completions = Completions {
+            ids: ["WEB-1", "WEB-2", "7"].map(str::to_string).to_vec(),
+            scopes: ["web", "work"].map(str::to_string).to_vec(),
+        };
+        assert_eq!(
+            completions.complete("com"),
+            (0, vec!["complete".to_string()])
+        );
+        assert_eq!(completions.complete("show WEB").1, ["WEB-1", "WEB-2"]);
+        assert_eq!(
+            completions.complete("ls -s w"),
+            (6, vec!["web".into(), "work".into()])
+        );
+        assert_eq!(completions.complete("scope set-key wo").1, ["work"]);
+    }
+}
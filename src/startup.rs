@@ -3,24 +3,23 @@ use std::fs;
 use anyhow::Context;
 use sqlx::{sqlite::SqliteConnectOptions, SqlitePool};
 
-use crate::{configuration::Settings, generator::Generator, tasks::get_last_id};
+use crate::configuration::Settings;
 
 pub struct Application {
     pub pool: SqlitePool,
-    pub generator: Generator,
+    pub settings: Settings,
 }
 
 impl Application {
     pub async fn build(configuration: Settings) -> anyhow::Result<Self> {
-        let options = SqliteConnectOptions::new().filename(configuration.location);
+        let options = SqliteConnectOptions::new().filename(&configuration.location);
         let pool = SqlitePool::connect_with(options)
             .await
             .expect("Failed to connect to the database");
-        let next_id = get_last_id(&pool)
-            .await?
-            .map_or(1, |previous_id| previous_id + 1);
-        let generator = Generator::from(next_id);
-        Ok(Self { pool, generator })
+        Ok(Self {
+            pool,
+            settings: configuration,
+        })
     }
 }
 
@@ -52,3 +51,15 @@ pub async fn ensure_db_created(configuration: &Settings) -> anyhow::Result<()> {
         .expect("Failed to migrate the database");
     Ok(())
 }
+
+/// Migrated in-memory database, on a single connection so every query sees the same data
+#[cfg(test)]
+pub async fn test_pool() -> SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+    pool
+}
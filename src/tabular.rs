@@ -1,60 +1,431 @@
-use std::fmt::Alignment;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    env,
+    fmt::{Alignment, Display},
+    io::{self, stdout, IsTerminal, Write},
+    rc::Rc,
+    str::FromStr,
+};
 
+use chrono::{Datelike, Local, NaiveDate};
 use itertools::Itertools;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
-use crate::domain::Task;
+use crate::{
+    agenda::DayCount,
+    dates::{format_age, format_relative},
+    domain::{Color, Scope, ScopeDetails, Task},
+    theme::{BorderStyle, Borders, Style, Theme, RESET},
+};
 
-fn format_string_to_constraint(text: &str, max_len: usize) -> &str {
-    let text_len = text.len();
-    if text_len <= max_len {
-        return text;
+/// Marker appended to values cut to fit their column
+const ELLIPSIS: &str = "…";
+
+/// Number of terminal columns taken by `text`, wide characters counting twice
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(grapheme_width).sum()
+}
+
+fn grapheme_width(grapheme: &str) -> usize {
+    // Combining marks and joiners are part of the cluster, which is as wide as its widest char
+    grapheme
+        .chars()
+        .map(|c| c.to_string().width())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Longest prefix of `text` fitting in `max_width` columns, never splitting grapheme clusters
+pub fn take_width(text: &str, max_width: usize) -> &str {
+    let mut width = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        width += grapheme_width(grapheme);
+        if width > max_width {
+            return &text[..index];
+        }
+    }
+    text
+}
+
+/// Cuts `text` to `max_width` columns, marking the cut with an ellipsis
+pub fn truncate_to_width(text: &str, max_width: usize) -> Cow<'_, str> {
+    if display_width(text) <= max_width {
+        return Cow::Borrowed(text);
+    }
+    match max_width.checked_sub(ELLIPSIS.width()) {
+        Some(available) => Cow::Owned(format!("{}{}", take_width(text, available), ELLIPSIS)),
+        None => Cow::Borrowed(take_width(text, max_width)),
     }
-    &text[0..max_len]
 }
 
-pub fn get_tasks_table(width: u16) -> ConsoleTableBuilder<Task> {
-    let id = TaskColumn::new("ID", |x| x.id.to_string()).set_data_alignment(Alignment::Right);
-    let description = TaskColumn::new("Description", |x| x.description.to_string());
-    let scope = TaskColumn::new("Scope", |x| match x.scope.as_ref() {
-        Some(v) => v.as_ref().to_string(),
-        None => "None".to_string(),
-    });
-    let created_at = TaskColumn::new("Created at", |x| {
-        x.created_at.format("%Y-%m-%d %H:%M:%S").to_string()
-    });
-    let completed_at = TaskColumn::new("Completed", |x| {
-        x.completed_at.map_or(" ", |_| "x").to_string()
-    });
+/// Splits `text` into lines of at most `width` columns, breaking between words when possible
+pub fn wrap_to_width(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut line_width = 0;
+    for mut word in text.split_whitespace() {
+        let mut word_width = display_width(word);
+        if line_width > 0 && line_width + 1 + word_width <= width {
+            line.push(' ');
+            line.push_str(word);
+            line_width += 1 + word_width;
+            continue;
+        }
+        if line_width > 0 {
+            lines.push(std::mem::take(&mut line));
+        }
+        while word_width > width {
+            let head = match take_width(word, width) {
+                "" => word.graphemes(true).next().unwrap_or(word),
+                head => head,
+            };
+            lines.push(head.to_string());
+            word = &word[head.len()..];
+            word_width = display_width(word);
+        }
+        line.push_str(word);
+        line_width = word_width;
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Pads `text` with spaces up to `width` columns
+pub fn pad_to_width(text: &str, width: usize, alignment: Alignment) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    let (left, right) = match alignment {
+        Alignment::Left => (0, padding),
+        Alignment::Right => (padding, 0),
+        Alignment::Center => (padding / 2, padding - padding / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
+
+/// Whether stdout is a terminal that should receive ANSI color codes
+pub fn supports_ansi_colors() -> bool {
+    env::var_os("NO_COLOR").is_none()
+        && env::var("TERM").map_or(true, |term| term != "dumb")
+        && stdout().is_terminal()
+}
 
-    ConsoleTableBuilder::<Task>::new(width)
-        .add_column(id, 1)
-        .add_column(description, 8)
-        .add_column(scope, 3)
-        .add_column(created_at, 4)
-        .add_column(completed_at, 2)
+pub fn paint(text: &str, color: Color) -> String {
+    Style::color(color).paint(text)
+}
+
+/// Emphasizes words of `text` matching search terms, prefix terms ending with `*`
+pub fn highlight_terms(text: &str, terms: &[String]) -> String {
+    let mut highlighted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(char::is_alphanumeric) {
+        highlighted.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(rest.len());
+        let word = &rest[..end];
+        let matched_len = terms
+            .iter()
+            .filter_map(|term| match_term(word, term))
+            .max()
+            .unwrap_or(0);
+        if matched_len > 0 {
+            highlighted.push_str(&format!(
+                "\x1b[1;4m{}{}{}",
+                &word[..matched_len],
+                RESET,
+                &word[matched_len..]
+            ));
+        } else {
+            highlighted.push_str(word);
+        }
+        rest = &rest[end..];
+    }
+    highlighted.push_str(rest);
+    highlighted
+}
+
+/// Length in bytes of the part of `word` matched by `term`
+fn match_term(word: &str, term: &str) -> Option<usize> {
+    let lowercase_word = word.to_lowercase();
+    match term.strip_suffix('*') {
+        Some(prefix) if !prefix.is_empty() && lowercase_word.starts_with(prefix) => word
+            .char_indices()
+            .nth(prefix.chars().count())
+            .map_or(Some(word.len()), |(i, _)| Some(i)),
+        None if lowercase_word == term => Some(word.len()),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TaskTableOptions {
+    /// Columns to display, the default ones when empty
+    pub columns: Vec<ColumnSpec>,
+    /// Color whole rows with their scope color instead of only the scope column
+    pub color_rows: bool,
+    /// Search terms emphasized in descriptions
+    pub highlighted_terms: Vec<String>,
+    /// Wrap long descriptions over several lines instead of truncating them
+    pub wrap: bool,
+}
+
+/// Named columns of the tasks table with their default weight
+const TASK_COLUMNS: [(&str, u16); 10] = [
+    ("id", 1),
+    ("description", 8),
+    ("scope", 3),
+    ("created", 4),
+    ("created_relative", 4),
+    ("age", 1),
+    ("done", 2),
+    ("completed", 4),
+    ("status", 2),
+    ("due", 2),
+];
+
+const DEFAULT_COLUMNS: [&str; 5] = ["id", "description", "scope", "created", "done"];
+
+/// Tasks table column picked by name, optionally with the weight it gets of the free space,
+/// e.g. `description:8`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSpec {
+    pub name: &'static str,
+    pub weight: u16,
+}
+
+impl FromStr for ColumnSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, weight) = match s.trim().split_once(':') {
+            Some((name, weight)) => (name, Some(weight)),
+            None => (s.trim(), None),
+        };
+        let name = name.to_lowercase();
+        let (name, default_weight) =
+            TASK_COLUMNS
+                .iter()
+                .find(|(n, _)| *n == name)
+                .ok_or_else(|| {
+                    let names: Vec<&str> = TASK_COLUMNS.iter().map(|(n, _)| *n).collect();
+                    format!(
+                        "unknown column '{}', expected one of: {}",
+                        name,
+                        names.join(", ")
+                    )
+                })?;
+        let weight = match weight {
+            Some(weight) => weight
+                .parse()
+                .ok()
+                .filter(|w| *w > 0)
+                .ok_or_else(|| format!("invalid width '{}' for column {}", weight, name))?,
+            None => *default_weight,
+        };
+        Ok(Self { name, weight })
+    }
+}
+
+pub fn get_tasks_table(
+    width: u16,
+    scopes: Vec<ScopeDetails>,
+    options: &TaskTableOptions,
+    theme: &Theme,
+) -> ConsoleTableBuilder<Task> {
+    let scopes: Rc<HashMap<Scope, ScopeDetails>> = Rc::new(
+        scopes
+            .into_iter()
+            .map(|details| (details.scope.clone(), details))
+            .collect(),
+    );
+    let scope_color = {
+        let scopes = scopes.clone();
+        move |x: &Task| {
+            x.scope
+                .as_ref()
+                .and_then(|s| scopes.get(s))
+                .and_then(|details| details.color)
+        }
+    };
+
+    let columns = match options.columns.is_empty() {
+        true => DEFAULT_COLUMNS.map(|name| name.parse().unwrap()).to_vec(),
+        false => options.columns.clone(),
+    };
+    let (completed, overdue) = (theme.completed, theme.overdue);
+    let mut builder = ConsoleTableBuilder::<Task>::new(width)
+        .set_border_style(theme.border)
+        .set_header_style(theme.header)
+        .set_row_style(move |x| match x.completed_at {
+            _ if x.is_overdue(Local::now()) => overdue,
+            Some(_) => completed,
+            None => Style::default(),
+        });
+    if options.color_rows {
+        builder = builder.set_row_color(scope_color.clone());
+    }
+    for spec in columns {
+        let mut column = get_task_column(spec.name, &scopes);
+        if spec.name == "description" && !options.highlighted_terms.is_empty() {
+            let terms = options.highlighted_terms.clone();
+            column = column.set_highlight(move |text| highlight_terms(text, &terms));
+        }
+        if spec.name == "description" {
+            column = column.set_wrap(options.wrap);
+        }
+        if spec.name == "scope" && !options.color_rows {
+            column = column.set_color(scope_color.clone());
+        }
+        builder = builder.add_column(column, spec.weight);
+    }
+    builder
+}
+
+fn get_task_column(name: &str, scopes: &Rc<HashMap<Scope, ScopeDetails>>) -> TaskColumn {
+    match name {
+        "id" => TaskColumn::new("ID", |x| x.display_id())
+            .set_data_alignment(Alignment::Right)
+            .set_max_width(12),
+        "description" => {
+            TaskColumn::new("Description", |x| x.description.to_string()).set_min_width(10)
+        }
+        "scope" => {
+            let scopes = scopes.clone();
+            TaskColumn::new("Scope", move |x| match x.scope.as_ref() {
+                Some(v) => scopes
+                    .get(v)
+                    .map_or_else(|| v.as_ref().to_string(), |details| details.display_name()),
+                None => "None".to_string(),
+            })
+            .set_min_width(5)
+        }
+        "created" => TaskColumn::new("Created at", |x| {
+            x.created_at.format("%Y-%m-%d %H:%M:%S").to_string()
+        })
+        .set_max_width(19),
+        "created_relative" => {
+            TaskColumn::new("Created", |x| format_relative(Local::now() - x.created_at))
+                .set_max_width(16)
+        }
+        "age" => TaskColumn::new("Age", |x| format_age(Local::now() - x.created_at))
+            .set_data_alignment(Alignment::Right)
+            .set_max_width(5),
+        "done" => TaskColumn::new("Completed", |x| {
+            x.completed_at.map_or(" ", |_| "x").to_string()
+        })
+        .set_max_width(9),
+        "completed" => TaskColumn::new("Completed at", |x| {
+            x.completed_at
+                .map_or(String::new(), |c| c.format("%Y-%m-%d %H:%M").to_string())
+        })
+        .set_max_width(16),
+        "status" => TaskColumn::new("Status", |x| x.status().to_string()).set_max_width(6),
+        "due" => TaskColumn::new("Due", |x| {
+            x.due_at
+                .map_or(String::new(), |d| d.format("%Y-%m-%d").to_string())
+        })
+        .set_max_width(10),
+        _ => unreachable!("column names are validated by ColumnSpec"),
+    }
+}
+
+/// Board of task cards showing their id and description
+pub fn get_task_board(width: u16, scopes: Vec<ScopeDetails>, theme: &Theme) -> ConsoleBoard<Task> {
+    let scopes: HashMap<Scope, ScopeDetails> = scopes
+        .into_iter()
+        .map(|details| (details.scope.clone(), details))
+        .collect();
+    let (completed, overdue) = (theme.completed, theme.overdue);
+    ConsoleBoard::new(
+        width,
+        |x: &Task| x.display_id(),
+        |x| x.description.to_string(),
+    )
+    .set_border_style(theme.border)
+    .set_header_style(theme.header)
+    .set_card_color(move |x| {
+        x.scope
+            .as_ref()
+            .and_then(|s| scopes.get(s))
+            .and_then(|details| details.color)
+    })
+    .set_card_style(move |x| match x.completed_at {
+        _ if x.is_overdue(Local::now()) => overdue,
+        Some(_) => completed,
+        None => Style::default(),
+    })
 }
 
 type TaskColumn = Column<Task>;
 
-type ColumnValueGetter<T> = fn(input: &T) -> String;
+type ColumnValueGetter<T> = Box<dyn Fn(&T) -> String>;
+
+type ColumnColorGetter<T> = Box<dyn Fn(&T) -> Option<Color>>;
+
+type CellHighlighter = Box<dyn Fn(&str) -> String>;
 
 pub struct Column<T> {
     pub name: String,
     pub column_alignment: Alignment,
     pub data_alignment: Alignment,
+    /// Whether values too long for the column span several lines instead of being truncated
+    pub wrap: bool,
+    /// Narrowest width the column is shrunk to when the table does not fit
+    pub min_width: usize,
+    /// Widest the column grows to, columns without one take the leftover space
+    pub max_width: Option<usize>,
     get_value: ColumnValueGetter<T>,
+    get_color: Option<ColumnColorGetter<T>>,
+    highlight: Option<CellHighlighter>,
 }
 
 impl<T> Column<T> {
-    pub fn new(name: &str, getter: ColumnValueGetter<T>) -> Self {
+    pub fn new(name: &str, getter: impl Fn(&T) -> String + 'static) -> Self {
         Self {
             name: name.to_string(),
             column_alignment: Alignment::Center,
             data_alignment: Alignment::Center,
-            get_value: getter,
+            wrap: false,
+            min_width: 1,
+            max_width: None,
+            get_value: Box::new(getter),
+            get_color: None,
+            highlight: None,
         }
     }
 
+    /// Colors the cells of this column, when the table is colored
+    pub fn set_color(mut self, getter: impl Fn(&T) -> Option<Color> + 'static) -> Self {
+        self.get_color = Some(Box::new(getter));
+        self
+    }
+
+    /// Decorates the already formatted cell text, when the table is colored
+    pub fn set_highlight(mut self, highlight: impl Fn(&str) -> String + 'static) -> Self {
+        self.highlight = Some(Box::new(highlight));
+        self
+    }
+
+    /// Wraps long values over several lines at word boundaries instead of truncating them
+    pub fn set_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn set_min_width(mut self, min_width: usize) -> Self {
+        self.min_width = min_width.max(1);
+        self
+    }
+
+    pub fn set_max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width.max(self.min_width));
+        self
+    }
+
     pub fn set_column_alignment(mut self, alignment: Alignment) -> Self {
         self.column_alignment = alignment;
         self
@@ -66,20 +437,32 @@ impl<T> Column<T> {
     }
 }
 
+type RowStyleGetter<T> = Box<dyn Fn(&T) -> Style>;
+
+/// Data row with the values of its cells
+type Row<T> = (T, Vec<String>);
+
+/// Rows under an optional title
+type Section<T> = (Option<String>, Vec<Row<T>>);
+
 pub struct ConsoleTable<T> {
     width: u16,
-    pub vertical_separator: char,
-    pub horizontal_separator: char,
-    pub cross_separator: char,
+    borders: Borders,
     columns: Vec<(Column<T>, u16)>,
+    colored: bool,
+    header_style: Style,
+    get_row_color: Option<ColumnColorGetter<T>>,
+    get_row_style: Option<RowStyleGetter<T>>,
 }
 
 pub struct ConsoleTableBuilder<T> {
     pub width: u16,
     columns: Vec<(Column<T>, u16)>,
-    vertical_separator: char,
-    horizontal_separator: char,
-    cross_separator: char,
+    border_style: BorderStyle,
+    colored: bool,
+    header_style: Style,
+    get_row_color: Option<ColumnColorGetter<T>>,
+    get_row_style: Option<RowStyleGetter<T>>,
 }
 
 impl<T> ConsoleTableBuilder<T> {
@@ -87,9 +470,11 @@ impl<T> ConsoleTableBuilder<T> {
         Self {
             width,
             columns: vec![],
-            vertical_separator: '|',
-            horizontal_separator: '-',
-            cross_separator: '+',
+            border_style: BorderStyle::Ascii,
+            colored: false,
+            header_style: Style::default(),
+            get_row_color: None,
+            get_row_style: None,
         }
     }
     pub fn add_column(mut self, column: Column<T>, weight: u16) -> Self {
@@ -97,6 +482,35 @@ impl<T> ConsoleTableBuilder<T> {
         self
     }
 
+    /// Enables ANSI colors when printing
+    pub fn set_colored(mut self, colored: bool) -> Self {
+        self.colored = colored;
+        self
+    }
+
+    pub fn set_border_style(mut self, border_style: BorderStyle) -> Self {
+        self.border_style = border_style;
+        self
+    }
+
+    /// Styles the column names, when the table is colored
+    pub fn set_header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Colors every cell of a row, taking precedence over column colors
+    pub fn set_row_color(mut self, getter: impl Fn(&T) -> Option<Color> + 'static) -> Self {
+        self.get_row_color = Some(Box::new(getter));
+        self
+    }
+
+    /// Styles every cell of a row on top of its colors, when the table is colored
+    pub fn set_row_style(mut self, getter: impl Fn(&T) -> Style + 'static) -> Self {
+        self.get_row_style = Some(Box::new(getter));
+        self
+    }
+
     pub fn build(self) -> Result<ConsoleTable<T>, ConsoleTableError> {
         if self.columns.len() >= 255 {
             return Err(ConsoleTableError::LengthExceeded);
@@ -105,100 +519,668 @@ impl<T> ConsoleTableBuilder<T> {
         if self.columns.is_empty() {
             return Err(ConsoleTableError::NoColumns);
         }
-        if self.width < self.get_min_width() {
+        let borders = self.border_style.borders();
+        if self.width < self.get_min_width(&borders) {
             return Err(ConsoleTableError::NotEnoughSpaceToPrint);
         }
 
         Ok(ConsoleTable {
             width: self.width,
+            borders,
             columns: self.columns,
-            vertical_separator: self.vertical_separator,
-            horizontal_separator: self.horizontal_separator,
-            cross_separator: self.cross_separator,
+            colored: self.colored,
+            header_style: self.header_style,
+            get_row_color: self.get_row_color,
+            get_row_style: self.get_row_style,
         })
     }
 
-    fn get_min_width(&self) -> u16 {
-        let column_len: u16 = self.columns.len().try_into().unwrap();
-        let spacing_witdh: u16 = column_len + 1;
-        let columns_width: u16 = self.columns.iter().map(|c| c.1).sum();
-        spacing_witdh + columns_width
+    fn get_min_width(&self, borders: &Borders) -> u16 {
+        let spacing_witdh: u16 = get_spacing_width(self.columns.len(), borders)
+            .try_into()
+            .unwrap();
+        let columns_width: usize = self.columns.iter().map(|c| c.0.min_width).sum();
+        spacing_witdh.saturating_add(columns_width.try_into().unwrap_or(u16::MAX))
+    }
+}
+
+/// Lines taken by the column names and the rules around them
+fn get_header_height(borders: &Borders) -> usize {
+    let rules = match (borders.horizontal, borders.outer) {
+        (None, _) => 0,
+        (Some(_), false) => 1,
+        (Some(_), true) => 2,
+    };
+    1 + rules
+}
+
+/// Width taken by the separators between and around `columns` columns
+fn get_spacing_width(columns: usize, borders: &Borders) -> usize {
+    match borders.outer {
+        true => columns + 1,
+        false => columns - 1,
     }
 }
 
 impl<T> ConsoleTable<T> {
-    pub fn print<I>(&self, data: I)
+    /// Writes the table to stdout
+    pub fn print<I>(&self, data: I) -> io::Result<()>
     where
         I: IntoIterator<Item = T>,
     {
-        self.print_separator();
-        self.print_header();
-        self.print_separator();
-        self.print_data(data);
-        self.print_separator();
+        self.render_to(&mut stdout().lock(), data)
     }
 
-    fn print_separator(&self) {
-        let unit_width = self.get_unit_width();
-        let separator_text: String = self
-            .columns
-            .iter()
-            .map(|x| {
-                let repeat_count: usize = (x.1 * unit_width).into();
-                self.horizontal_separator.to_string().repeat(repeat_count)
+    /// Renders the table as text, one line per table line
+    pub fn to_string<I>(&self, data: I) -> String
+    where
+        I: IntoIterator<Item = T>,
+    {
+        render_to_string(|buffer| self.render_to(buffer, data))
+    }
+
+    /// Renders the table split in sections, see [`ConsoleTable::render_grouped_to`]
+    pub fn to_grouped_string<G, I>(&self, groups: G) -> String
+    where
+        G: IntoIterator<Item = (String, I)>,
+        I: IntoIterator<Item = T>,
+    {
+        render_to_string(|buffer| self.render_grouped_to(buffer, groups))
+    }
+
+    /// Number of lines above the first data row
+    pub fn header_height(&self) -> usize {
+        get_header_height(&self.borders)
+    }
+
+    pub fn render_to<W, I>(&self, writer: &mut W, data: I) -> io::Result<()>
+    where
+        W: Write,
+        I: IntoIterator<Item = T>,
+    {
+        self.render_sections(writer, vec![(None, self.get_rows(data))])
+    }
+
+    /// Writes the table to stdout, split in sections headed by their title
+    pub fn print_grouped<G, I>(&self, groups: G) -> io::Result<()>
+    where
+        G: IntoIterator<Item = (String, I)>,
+        I: IntoIterator<Item = T>,
+    {
+        self.render_grouped_to(&mut stdout().lock(), groups)
+    }
+
+    /// Renders one section per group, each headed by a title row spanning the table.
+    /// Columns are sized once for all groups so that they stay aligned.
+    pub fn render_grouped_to<W, G, I>(&self, writer: &mut W, groups: G) -> io::Result<()>
+    where
+        W: Write,
+        G: IntoIterator<Item = (String, I)>,
+        I: IntoIterator<Item = T>,
+    {
+        let sections = groups
+            .into_iter()
+            .map(|(title, data)| (Some(title), self.get_rows(data)))
+            .collect();
+        self.render_sections(writer, sections)
+    }
+
+    fn get_rows<I>(&self, data: I) -> Vec<Row<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        data.into_iter()
+            .map(|row| {
+                let values = self.columns.iter().map(|x| (x.0.get_value)(&row)).collect();
+                (row, values)
             })
-            .join(&self.cross_separator.to_string());
-        let separator_text: String =
-            add_value_to_start_and_end_of_string(separator_text, &self.cross_separator.to_string());
-        println!("{}", separator_text);
+            .collect()
+    }
+
+    fn render_sections<W: Write>(
+        &self,
+        writer: &mut W,
+        sections: Vec<Section<T>>,
+    ) -> io::Result<()> {
+        let widths = self.get_column_widths(sections.iter().flat_map(|(_, rows)| rows));
+        if self.borders.outer {
+            write_separator(writer, &self.borders, &widths, self.borders.top)?;
+        }
+        self.write_header(writer, &widths)?;
+        write_separator(writer, &self.borders, &widths, self.borders.middle)?;
+        for (i, (title, rows)) in sections.iter().enumerate() {
+            if let Some(title) = title {
+                if i > 0 {
+                    write_separator(writer, &self.borders, &widths, self.borders.middle)?;
+                }
+                self.write_title(writer, &widths, title)?;
+                write_separator(writer, &self.borders, &widths, self.borders.middle)?;
+            }
+            self.write_data(writer, rows, &widths)?;
+        }
+        if self.borders.outer {
+            write_separator(writer, &self.borders, &widths, self.borders.bottom)?;
+        }
+        Ok(())
+    }
+
+    fn write_header<W: Write>(&self, writer: &mut W, widths: &[usize]) -> io::Result<()> {
+        let cells = self.columns.iter().zip(widths).map(|(x, width)| {
+            let cell = get_formatted_cell(x.0.name.as_ref(), *width, x.0.column_alignment);
+            match self.colored {
+                true => self.header_style.paint(&cell),
+                false => cell,
+            }
+        });
+        writeln!(writer, "{}", join_cells(&self.borders, cells))
+    }
+
+    fn write_title<W: Write>(
+        &self,
+        writer: &mut W,
+        widths: &[usize],
+        title: &str,
+    ) -> io::Result<()> {
+        let width = widths.iter().sum::<usize>() + widths.len() - 1;
+        let cell = get_formatted_cell(title, width, Alignment::Left);
+        let cell = match self.colored {
+            true => self.header_style.paint(&cell),
+            false => cell,
+        };
+        writeln!(writer, "{}", join_cells(&self.borders, [cell]))
+    }
+
+    fn write_data<W: Write>(
+        &self,
+        writer: &mut W,
+        rows: &[Row<T>],
+        widths: &[usize],
+    ) -> io::Result<()> {
+        for (row, values) in rows {
+            let row_color = self.get_color(row, self.get_row_color.as_ref());
+            let row_style = match (self.colored, self.get_row_style.as_ref()) {
+                (true, Some(get_style)) => get_style(row),
+                _ => Style::default(),
+            };
+            let cells: Vec<Vec<String>> = self
+                .columns
+                .iter()
+                .zip(values)
+                .zip(widths)
+                .map(|((x, value), width)| match x.0.wrap {
+                    true => wrap_to_width(value, *width),
+                    false => vec![value.clone()],
+                })
+                .collect();
+            let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+            for line in 0..height {
+                let line_cells =
+                    self.columns
+                        .iter()
+                        .zip(&cells)
+                        .zip(widths)
+                        .map(|((x, lines), width)| {
+                            let value = lines.get(line).map_or("", String::as_str);
+                            let mut cell = get_formatted_cell(value, *width, x.0.data_alignment);
+                            if let Some(highlight) = x.0.highlight.as_ref().filter(|_| self.colored)
+                            {
+                                cell = highlight(&cell);
+                            }
+                            let color =
+                                row_color.or_else(|| self.get_color(row, x.0.get_color.as_ref()));
+                            let style = Style {
+                                color,
+                                ..Default::default()
+                            };
+                            style.patch(row_style).paint(&cell)
+                        });
+                writeln!(writer, "{}", join_cells(&self.borders, line_cells))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_color(&self, row: &T, getter: Option<&ColumnColorGetter<T>>) -> Option<Color> {
+        if !self.colored {
+            return None;
+        }
+        getter.and_then(|get_color| get_color(row))
     }
 
-    fn print_header(&self) {
-        let unit_width = self.get_unit_width();
-        let column_header_text: String = self
+    fn get_column_widths<'a>(&self, rows: impl Iterator<Item = &'a Row<T>>) -> Vec<usize>
+    where
+        T: 'a,
+    {
+        let mut content_widths: Vec<usize> = self
+            .columns
+            .iter()
+            .map(|(column, _)| display_width(&column.name))
+            .collect();
+        for (_, values) in rows {
+            for (content_width, value) in content_widths.iter_mut().zip(values) {
+                *content_width = (*content_width).max(display_width(value));
+            }
+        }
+        let layouts: Vec<ColumnLayout> = self
             .columns
             .iter()
-            .map(|x| {
-                let width: usize = (x.1 * unit_width).into();
-                get_formatted_cell(x.0.name.as_ref(), width, x.0.column_alignment)
+            .zip(content_widths)
+            .map(|((column, weight), content_width)| ColumnLayout {
+                content_width,
+                min_width: column.min_width,
+                max_width: column.max_width,
+                weight: *weight,
             })
-            .join(&self.vertical_separator.to_string());
-        let column_header_text = add_value_to_start_and_end_of_string(
-            column_header_text,
-            &self.vertical_separator.to_string(),
-        );
-        println!("{}", column_header_text);
+            .collect();
+        let spacing_width = get_spacing_width(self.columns.len(), &self.borders);
+        layout_columns(usize::from(self.width) - spacing_width, &layouts)
     }
+}
 
-    fn print_data<I>(&self, data: I)
+/// Sizing constraints of a column, measured from its content
+#[derive(Debug, Clone, Copy)]
+struct ColumnLayout {
+    content_width: usize,
+    min_width: usize,
+    max_width: Option<usize>,
+    weight: u16,
+}
+
+impl ColumnLayout {
+    fn max_width(&self) -> usize {
+        self.max_width.unwrap_or(usize::MAX)
+    }
+}
+
+/// Splits `available` columns between table columns.
+///
+/// Columns start at their content width within their min and max widths. When that is too
+/// wide, the column with the most width per weight unit shrinks first, down to its min width.
+/// Otherwise the leftover space goes, by weight, to the columns without a max width, or to the
+/// ones below their max width when every column has one.
+fn layout_columns(available: usize, columns: &[ColumnLayout]) -> Vec<usize> {
+    let mut widths: Vec<usize> = columns
+        .iter()
+        .map(|c| c.content_width.clamp(c.min_width, c.max_width()))
+        .collect();
+    let total: usize = widths.iter().sum();
+    if total > available {
+        for _ in available..total {
+            let shrinkable = (0..columns.len())
+                .filter(|i| widths[*i] > columns[*i].min_width)
+                .max_by(|a, b| {
+                    let a = widths[*a] as f64 / f64::from(columns[*a].weight.max(1));
+                    let b = widths[*b] as f64 / f64::from(columns[*b].weight.max(1));
+                    a.total_cmp(&b)
+                });
+            match shrinkable {
+                Some(i) => widths[i] -= 1,
+                None => break,
+            }
+        }
+        return widths;
+    }
+    let mut leftover = available - total;
+    let flexible: Vec<usize> = match columns.iter().any(|c| c.max_width.is_none()) {
+        true => (0..columns.len())
+            .filter(|i| columns[*i].max_width.is_none())
+            .collect(),
+        false => (0..columns.len()).collect(),
+    };
+    while leftover > 0 {
+        let growable: Vec<usize> = flexible
+            .iter()
+            .copied()
+            .filter(|i| widths[*i] < columns[*i].max_width())
+            .collect();
+        if growable.is_empty() {
+            break;
+        }
+        let total_weight: usize = growable
+            .iter()
+            .map(|i| usize::from(columns[*i].weight.max(1)))
+            .sum();
+        let mut given = 0;
+        for i in &growable {
+            let share = leftover * usize::from(columns[*i].weight.max(1)) / total_weight;
+            let share = share.min(columns[*i].max_width() - widths[*i]);
+            widths[*i] += share;
+            given += share;
+        }
+        if given == 0 {
+            // Spread the remainder one column at a time, heaviest columns first
+            let heaviest = growable
+                .iter()
+                .max_by_key(|i| columns[**i].weight)
+                .copied()
+                .unwrap();
+            widths[heaviest] += 1;
+            given = 1;
+        }
+        leftover -= given;
+    }
+    widths
+}
+
+/// Cards laid out in side by side lanes, such as a kanban board
+pub struct ConsoleBoard<T> {
+    width: u16,
+    borders: Borders,
+    colored: bool,
+    header_style: Style,
+    get_title: ColumnValueGetter<T>,
+    get_body: ColumnValueGetter<T>,
+    get_card_color: Option<ColumnColorGetter<T>>,
+    get_card_style: Option<RowStyleGetter<T>>,
+}
+
+impl<T> ConsoleBoard<T> {
+    /// Board whose cards show a title line above a body wrapped to the lane width
+    pub fn new(
+        width: u16,
+        get_title: impl Fn(&T) -> String + 'static,
+        get_body: impl Fn(&T) -> String + 'static,
+    ) -> Self {
+        Self {
+            width,
+            borders: BorderStyle::Ascii.borders(),
+            colored: false,
+            header_style: Style::default(),
+            get_title: Box::new(get_title),
+            get_body: Box::new(get_body),
+            get_card_color: None,
+            get_card_style: None,
+        }
+    }
+
+    pub fn set_border_style(mut self, border_style: BorderStyle) -> Self {
+        self.borders = border_style.borders();
+        self
+    }
+
+    /// Paints lane names and cards with their styles and colors
+    pub fn set_colored(mut self, colored: bool) -> Self {
+        self.colored = colored;
+        self
+    }
+
+    /// Styles the lane names and card titles, when the board is colored
+    pub fn set_header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Colors cards, when the board is colored
+    pub fn set_card_color(mut self, getter: impl Fn(&T) -> Option<Color> + 'static) -> Self {
+        self.get_card_color = Some(Box::new(getter));
+        self
+    }
+
+    /// Styles cards on top of their color, when the board is colored
+    pub fn set_card_style(mut self, getter: impl Fn(&T) -> Style + 'static) -> Self {
+        self.get_card_style = Some(Box::new(getter));
+        self
+    }
+
+    /// Number of lines above the first card
+    pub fn header_height(&self) -> usize {
+        get_header_height(&self.borders)
+    }
+
+    /// Renders the board as text, one lane per item of `lanes`
+    pub fn to_string<L, I>(&self, lanes: L) -> String
     where
+        L: IntoIterator<Item = (String, I)>,
         I: IntoIterator<Item = T>,
     {
-        let unit_width = self.get_unit_width();
-        for row in data.into_iter() {
-            let data_text: String = self
-                .columns
+        render_to_string(|buffer| self.render_to(buffer, lanes))
+    }
+
+    /// Renders one lane per item of `lanes`, sharing the board width between them
+    pub fn render_to<W, L, I>(&self, writer: &mut W, lanes: L) -> io::Result<()>
+    where
+        W: Write,
+        L: IntoIterator<Item = (String, I)>,
+        I: IntoIterator<Item = T>,
+    {
+        let lanes: Vec<(String, Vec<T>)> = lanes
+            .into_iter()
+            .map(|(name, cards)| (name, cards.into_iter().collect()))
+            .collect();
+        if lanes.is_empty() {
+            return Ok(());
+        }
+        let widths = self.get_lane_widths(lanes.len());
+        let headers: Vec<String> = lanes
+            .iter()
+            .zip(&widths)
+            .map(|((name, _), width)| {
+                let cell = get_formatted_cell(name, *width, Alignment::Center);
+                self.paint(&cell, self.header_style)
+            })
+            .collect();
+        let columns: Vec<Vec<String>> = lanes
+            .iter()
+            .zip(&widths)
+            .map(|((_, cards), width)| self.get_lane_lines(cards, *width))
+            .collect();
+        let height = columns.iter().map(Vec::len).max().unwrap_or(0);
+        if self.borders.outer {
+            write_separator(writer, &self.borders, &widths, self.borders.top)?;
+        }
+        writeln!(writer, "{}", join_cells(&self.borders, headers))?;
+        write_separator(writer, &self.borders, &widths, self.borders.middle)?;
+        for line in 0..height {
+            let cells = columns
                 .iter()
-                .map(|x| {
-                    let width: usize = (x.1 * unit_width).into();
-                    let value = (x.0.get_value)(&row);
-                    get_formatted_cell(&value, width, x.0.data_alignment)
-                })
-                .join(&self.vertical_separator.to_string());
-            let column_header_text = add_value_to_start_and_end_of_string(
-                data_text,
-                &self.vertical_separator.to_string(),
-            );
-            println!("{}", column_header_text);
+                .zip(&widths)
+                .map(|(lines, width)| match lines.get(line) {
+                    Some(cell) => cell.clone(),
+                    None => " ".repeat(*width),
+                });
+            writeln!(writer, "{}", join_cells(&self.borders, cells))?;
+        }
+        if self.borders.outer {
+            write_separator(writer, &self.borders, &widths, self.borders.bottom)?;
+        }
+        Ok(())
+    }
+
+    /// Splits the width evenly between lanes, the first lanes taking the remainder
+    fn get_lane_widths(&self, lanes: usize) -> Vec<usize> {
+        let spacing_width = get_spacing_width(lanes, &self.borders);
+        let available = usize::from(self.width).saturating_sub(spacing_width);
+        (0..lanes)
+            .map(|i| (available / lanes + usize::from(i < available % lanes)).max(1))
+            .collect()
+    }
+
+    /// Padded lines of the cards of a lane, cards being separated by an empty line
+    fn get_lane_lines(&self, cards: &[T], width: usize) -> Vec<String> {
+        // Keeps a space on both sides of the card text when there is room for it
+        let (margin, text_width) = match width {
+            0..=4 => ("", width),
+            _ => (" ", width - 2),
+        };
+        let mut lines = vec![];
+        for (i, card) in cards.iter().enumerate() {
+            if i > 0 {
+                lines.push(" ".repeat(width));
+            }
+            let color = match (self.colored, self.get_card_color.as_ref()) {
+                (true, Some(get_color)) => get_color(card),
+                _ => None,
+            };
+            let style = Style {
+                color,
+                ..Default::default()
+            };
+            let style = match (self.colored, self.get_card_style.as_ref()) {
+                (true, Some(get_style)) => style.patch(get_style(card)),
+                _ => style,
+            };
+            let title = (self.get_title)(card);
+            let title = get_formatted_cell(&title, text_width, Alignment::Left);
+            lines.push(format!(
+                "{}{}{}",
+                margin,
+                self.paint(&title, style.patch(self.header_style)),
+                margin
+            ));
+            for body_line in wrap_to_width(&(self.get_body)(card), text_width) {
+                let body_line = get_formatted_cell(&body_line, text_width, Alignment::Left);
+                lines.push(format!(
+                    "{}{}{}",
+                    margin,
+                    self.paint(&body_line, style),
+                    margin
+                ));
+            }
+        }
+        lines
+    }
+
+    fn paint(&self, text: &str, style: Style) -> String {
+        match self.colored {
+            true => style.paint(text),
+            false => text.to_string(),
+        }
+    }
+}
+
+/// Width of the day cells of a calendar, fitting `99 tasks`
+const CALENDAR_CELL_WIDTH: usize = 10;
+
+/// Month grid starting weeks on Monday, showing the number of tasks of each day
+pub struct ConsoleCalendar {
+    borders: Borders,
+    colored: bool,
+    header_style: Style,
+    today: Option<NaiveDate>,
+}
+
+impl Default for ConsoleCalendar {
+    fn default() -> Self {
+        Self {
+            borders: BorderStyle::Ascii.borders(),
+            colored: false,
+            header_style: Style::default(),
+            today: None,
+        }
+    }
+}
+
+impl ConsoleCalendar {
+    pub fn set_border_style(mut self, border_style: BorderStyle) -> Self {
+        self.borders = border_style.borders();
+        self
+    }
+
+    /// Paints the header and the cell of today with their styles
+    pub fn set_colored(mut self, colored: bool) -> Self {
+        self.colored = colored;
+        self
+    }
+
+    /// Styles the month name and the week days, when the calendar is colored
+    pub fn set_header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Underlines the cell of a day, when the calendar is colored
+    pub fn set_today(mut self, today: NaiveDate) -> Self {
+        self.today = Some(today);
+        self
+    }
+
+    /// Number of lines above the first week
+    pub fn header_height(&self) -> usize {
+        get_header_height(&self.borders) + 1
+    }
+
+    /// Renders the month of `days` as text
+    pub fn to_string(&self, days: &[DayCount]) -> String {
+        render_to_string(|buffer| self.render_to(buffer, days))
+    }
+
+    /// Renders the month of `days`, which are the consecutive days of a single month
+    pub fn render_to<W: Write>(&self, writer: &mut W, days: &[DayCount]) -> io::Result<()> {
+        let Some(first) = days.first() else {
+            return Ok(());
+        };
+        let widths = [CALENDAR_CELL_WIDTH; 7];
+        let width = CALENDAR_CELL_WIDTH * 7 + get_spacing_width(7, &self.borders);
+        let title = pad_to_width(
+            &first.date.format("%B %Y").to_string(),
+            width,
+            Alignment::Center,
+        );
+        writeln!(
+            writer,
+            "{}",
+            self.paint(title.trim_end(), self.header_style)
+        )?;
+        if self.borders.outer {
+            write_separator(writer, &self.borders, &widths, self.borders.top)?;
+        }
+        let weekdays = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+            .map(|day| {
+                let cell = pad_to_width(day, CALENDAR_CELL_WIDTH, Alignment::Center);
+                self.paint(&cell, self.header_style)
+            })
+            .to_vec();
+        writeln!(writer, "{}", join_cells(&self.borders, weekdays))?;
+        let offset = first.date.weekday().num_days_from_monday() as usize;
+        let mut cells: Vec<Option<&DayCount>> = vec![None; offset];
+        cells.extend(days.iter().map(Some));
+        cells.resize(cells.len().div_ceil(7) * 7, None);
+        for week in cells.chunks(7) {
+            write_separator(writer, &self.borders, &widths, self.borders.middle)?;
+            let (numbers, counts): (Vec<String>, Vec<String>) =
+                week.iter().map(|day| self.get_day_lines(*day)).unzip();
+            writeln!(writer, "{}", join_cells(&self.borders, numbers))?;
+            writeln!(writer, "{}", join_cells(&self.borders, counts))?;
+        }
+        if self.borders.outer {
+            write_separator(writer, &self.borders, &widths, self.borders.bottom)?;
         }
+        Ok(())
     }
 
-    fn get_unit_width(&self) -> u16 {
-        let column_len: u16 = self.columns.len().try_into().unwrap();
-        let spacing_witdh: u16 = column_len + 1;
+    /// Day number and task count of a cell, blank outside of the month
+    fn get_day_lines(&self, day: Option<&DayCount>) -> (String, String) {
+        let Some(day) = day else {
+            let blank = " ".repeat(CALENDAR_CELL_WIDTH);
+            return (blank.clone(), blank);
+        };
+        let number = format!("{} ", day.date.day());
+        let count = match day.count {
+            0 => String::new(),
+            1 => " 1 task".to_string(),
+            count => format!(" {} tasks", count),
+        };
+        let style = match self.today == Some(day.date) {
+            true => Style {
+                underline: true,
+                ..self.header_style
+            },
+            false => Style::default(),
+        };
+        (
+            self.paint(
+                &get_formatted_cell(&number, CALENDAR_CELL_WIDTH, Alignment::Right),
+                style,
+            ),
+            get_formatted_cell(&count, CALENDAR_CELL_WIDTH, Alignment::Left),
+        )
+    }
 
-        let total_units: u16 = self.columns.iter().map(|c| c.1).sum();
-        (self.width - spacing_witdh) / total_units
+    fn paint(&self, text: &str, style: Style) -> String {
+        match self.colored {
+            true => style.paint(text),
+            false => text.to_string(),
+        }
     }
 }
 
@@ -209,6 +1191,57 @@ pub enum ConsoleTableError {
     NoColumns,
 }
 
+impl Display for ConsoleTableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConsoleTableError::LengthExceeded => write!(f, "too many columns"),
+            ConsoleTableError::NotEnoughSpaceToPrint => {
+                write!(f, "the columns do not fit in the terminal width")
+            }
+            ConsoleTableError::NoColumns => write!(f, "no columns to print"),
+        }
+    }
+}
+
+impl std::error::Error for ConsoleTableError {}
+
+/// Text written by `render` into memory
+fn render_to_string(render: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+    let mut buffer = Vec::new();
+    render(&mut buffer).expect("writing to memory does not fail");
+    String::from_utf8(buffer).expect("renderers write valid UTF-8")
+}
+
+/// Writes a horizontal line of the given borders below cells of the given widths
+fn write_separator<W: Write>(
+    writer: &mut W,
+    borders: &Borders,
+    widths: &[usize],
+    [left, cross, right]: [char; 3],
+) -> io::Result<()> {
+    let Some(horizontal) = borders.horizontal else {
+        return Ok(());
+    };
+    let separator_text: String = widths
+        .iter()
+        .map(|width| horizontal.to_string().repeat(*width))
+        .join(&cross.to_string());
+    match borders.outer {
+        true => writeln!(writer, "{}{}{}", left, separator_text, right),
+        false => writeln!(writer, "{}", separator_text),
+    }
+}
+
+/// Joins the cells of a line with the vertical borders
+fn join_cells(borders: &Borders, cells: impl IntoIterator<Item = String>) -> String {
+    let vertical = borders.vertical.to_string();
+    let text = cells.into_iter().join(&vertical);
+    match borders.outer {
+        true => add_value_to_start_and_end_of_string(text, &vertical),
+        false => text,
+    }
+}
+
 fn add_value_to_start_and_end_of_string(mut text: String, value_to_add: &str) -> String {
     text.insert_str(0, value_to_add);
     text.push_str(value_to_add);
@@ -216,21 +1249,285 @@ fn add_value_to_start_and_end_of_string(mut text: String, value_to_add: &str) ->
 }
 
 fn get_formatted_cell(value: &str, width: usize, alignment: Alignment) -> String {
-    match alignment {
-        Alignment::Left => format!(
-            "{:<width$}",
-            format_string_to_constraint(value, width),
-            width = width
-        ),
-        Alignment::Right => format!(
-            "{:>width$}",
-            format_string_to_constraint(value, width),
-            width = width
-        ),
-        Alignment::Center => format!(
-            "{:^width$}",
-            format_string_to_constraint(value, width),
-            width = width
-        ),
+    pad_to_width(&truncate_to_width(value, width), width, alignment)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn tasks() -> Vec<Task> {
+        let completed_at = Local.with_ymd_and_hms(2024, 8, 5, 10, 30, 0).unwrap();
+        vec![
+            Task::test(1, "Write the release notes for 1.0").set_scope(Some("work")),
+            Task::test(2, "Réserver le café").set_completed_at(Some(completed_at)),
+            Task::test(3, "日本語の説明").set_scope(Some("開発")),
+        ]
+    }
+
+    #[test]
+    fn tasks_table_snapshot() {
+        let scopes = vec![ScopeDetails {
+            scope: Scope::new("work".to_string()),
+            color: Some(Color::Cyan),
+            label: Some("W".to_string()),
+            key: None,
+        }];
+        let options = TaskTableOptions {
+            columns: "id,description,scope,done"
+                .split(',')
+                .map(|c| c.parse().unwrap())
+                .collect(),
+            ..Default::default()
+        };
+        let table = get_tasks_table(50, scopes, &options, &Theme::default())
+            .build()
+            .unwrap();
+        assert_eq!(table.to_string(tasks()), SNAPSHOT_TASKS);
+    }
+
+    const SNAPSHOT_TASKS: &str = r"+--+------------------------------+------+-------+
+|ID|         Description          |Scope |Comple…|
++--+------------------------------+------+-------+
+| 1|Write the release notes for 1…|W work|       |
+| 2|       Réserver le café       | None |   x   |
+| 3|         日本語の説明         | 開発 |       |
++--+------------------------------+------+-------+
+";
+
+    #[test]
+    fn wrapped_rounded_table_snapshot() {
+        let options = TaskTableOptions {
+            columns: vec!["id".parse().unwrap(), "description".parse().unwrap()],
+            wrap: true,
+            ..Default::default()
+        };
+        let theme = Theme {
+            border: BorderStyle::Rounded,
+            ..Default::default()
+        };
+        let table = get_tasks_table(24, vec![], &options, &theme)
+            .build()
+            .unwrap();
+        assert_eq!(table.to_string(tasks()), SNAPSHOT_WRAPPED);
+    }
+
+    const SNAPSHOT_WRAPPED: &str = r"╭──┬───────────────────╮
+│ID│    Description    │
+├──┼───────────────────┤
+│ 1│ Write the release │
+│  │   notes for 1.0   │
+│ 2│ Réserver le café  │
+│ 3│   日本語の説明    │
+╰──┴───────────────────╯
+";
+
+    #[test]
+    fn colored_table_snapshot() {
+        let options = TaskTableOptions {
+            columns: vec!["id".parse().unwrap(), "description".parse().unwrap()],
+            ..Default::default()
+        };
+        let theme = Theme {
+            border: BorderStyle::Minimal,
+            ..Default::default()
+        };
+        let table = get_tasks_table(24, vec![], &options, &theme)
+            .set_colored(true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            table.to_string(tasks().into_iter().skip(1)),
+            SNAPSHOT_COLORED
+        );
+    }
+
+    const SNAPSHOT_COLORED: &str = concat!(
+        "\x1b[1mID\x1b[0m \x1b[1m     Description     \x1b[0m\n",
+        "── ─────────────────────\n",
+        "\x1b[2m 2\x1b[0m \x1b[2m  Réserver le café   \x1b[0m\n",
+        " 3     日本語の説明     \n",
+    );
+
+    #[test]
+    fn grouped_table_snapshot() {
+        let table = ConsoleTableBuilder::<u32>::new(20)
+            .add_column(Column::new("Number", |x: &u32| x.to_string()), 1)
+            .build()
+            .unwrap();
+        let groups = [
+            ("odd (2)".to_string(), vec![1, 3]),
+            ("even (1)".to_string(), vec![2]),
+        ];
+        assert_eq!(table.to_grouped_string(groups), SNAPSHOT_GROUPED);
+    }
+
+    const SNAPSHOT_GROUPED: &str = r"+------------------+
+|      Number      |
++------------------+
+|odd (2)           |
++------------------+
+|        1         |
+|        3         |
++------------------+
+|even (1)          |
++------------------+
+|        2         |
++------------------+
+";
+
+    #[test]
+    fn board_snapshot() {
+        let board = get_task_board(40, vec![], &Theme::default());
+        let (done, open): (Vec<Task>, Vec<Task>) =
+            tasks().into_iter().partition(|x| x.completed_at.is_some());
+        let lanes = [
+            ("open (2)".to_string(), open),
+            ("done (1)".to_string(), done),
+        ];
+        assert_eq!(board.to_string(lanes), SNAPSHOT_BOARD);
+    }
+
+    const SNAPSHOT_BOARD: &str = r"+-------------------+------------------+
+|     open (2)      |     done (1)     |
++-------------------+------------------+
+| 1                 | 2                |
+| Write the release | Réserver le café |
+| notes for 1.0     |                  |
+|                   |                  |
+| 3                 |                  |
+| 日本語の説明      |                  |
++-------------------+------------------+
+";
+
+    #[test]
+    fn columns_capture_their_context() {
+        let labels: HashMap<u32, &str> = HashMap::from([(1, "one"), (2, "two")]);
+        let table = ConsoleTableBuilder::<u32>::new(20)
+            .add_column(Column::new("Number", |x: &u32| x.to_string()), 1)
+            .add_column(
+                Column::new("Label", move |x: &u32| {
+                    labels.get(x).unwrap_or(&"?").to_string()
+                })
+                .set_data_alignment(Alignment::Left),
+                1,
+            )
+            .set_border_style(BorderStyle::None)
+            .build()
+            .unwrap();
+        assert_eq!(table.to_string([1, 2, 3]), SNAPSHOT_CONTEXT);
+    }
+
+    const SNAPSHOT_CONTEXT: &str = r"  Number     Label  
+    1      one      
+    2      two      
+    3      ?        
+";
+
+    #[test]
+    fn wide_and_combined_characters_are_measured_by_display_width() {
+        assert_eq!(display_width("task"), 4);
+        assert_eq!(display_width("café"), 4);
+        assert_eq!(display_width("cafe\u{301}"), 4);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("👨‍👩‍👧"), 2);
+    }
+
+    #[test]
+    fn truncated_cells_end_with_an_ellipsis() {
+        assert_eq!(
+            get_formatted_cell("Write docs", 6, Alignment::Left),
+            "Write…"
+        );
+        assert_eq!(
+            get_formatted_cell("Écrire la doc", 7, Alignment::Left),
+            "Écrire…"
+        );
+        assert_eq!(
+            get_formatted_cell("日本語の説明", 7, Alignment::Left),
+            "日本語…"
+        );
+        assert_eq!(
+            get_formatted_cell("日本語の説明", 6, Alignment::Left),
+            "日本… "
+        );
+        assert_eq!(
+            get_formatted_cell("cafe\u{301}s", 4, Alignment::Left),
+            "caf…"
+        );
+    }
+
+    #[test]
+    fn wrapped_text_breaks_between_words() {
+        assert_eq!(
+            wrap_to_width("Write the release notes", 10),
+            ["Write the", "release", "notes"]
+        );
+        assert_eq!(
+            wrap_to_width("Réécrire la documentation", 8),
+            ["Réécrire", "la", "document", "ation"]
+        );
+        assert_eq!(
+            wrap_to_width("日本語の説明 です", 6),
+            ["日本語", "の説明", "です"]
+        );
+        assert_eq!(wrap_to_width("", 6), [""]);
+        assert_eq!(wrap_to_width("日本", 1), ["日", "本"]);
+    }
+
+    fn layout(content_width: usize, max_width: Option<usize>, weight: u16) -> ColumnLayout {
+        ColumnLayout {
+            content_width,
+            min_width: 3,
+            max_width,
+            weight,
+        }
+    }
+
+    #[test]
+    fn leftover_space_goes_to_flexible_columns() {
+        let columns = [
+            layout(5, Some(12), 1),
+            layout(20, None, 8),
+            layout(4, None, 3),
+            layout(19, Some(19), 4),
+        ];
+        let widths = layout_columns(100, &columns);
+        assert_eq!(widths, [5, 58, 18, 19]);
+        assert_eq!(widths.iter().sum::<usize>(), 100);
+
+        let columns = [layout(5, Some(12), 1), layout(6, Some(9), 2)];
+        assert_eq!(layout_columns(30, &columns), [12, 9]);
+        assert_eq!(layout_columns(18, &columns), [9, 9]);
+    }
+
+    #[test]
+    fn widest_columns_shrink_first_down_to_their_min_width() {
+        let columns = [
+            layout(5, Some(12), 1),
+            layout(80, None, 8),
+            layout(30, None, 3),
+        ];
+        let widths = layout_columns(60, &columns);
+        assert_eq!(widths.iter().sum::<usize>(), 60);
+        assert_eq!(widths[0], 5);
+        assert!(widths[1] > widths[2]);
+        assert_eq!(layout_columns(9, &columns), [3, 3, 3]);
+    }
+
+    #[test]
+    fn non_ascii_cells_are_padded_to_the_column_width() {
+        assert_eq!(get_formatted_cell("ops", 7, Alignment::Center), "  ops  ");
+        assert_eq!(get_formatted_cell("🛠 web", 7, Alignment::Left), "🛠 web  ");
+        assert_eq!(get_formatted_cell("開発", 7, Alignment::Center), " 開発  ");
+        assert_eq!(get_formatted_cell("Ü1", 4, Alignment::Right), "  Ü1");
+        for cell in ["日本語の説明", "🛠 web", "cafe\u{301}", "x"] {
+            assert_eq!(
+                display_width(&get_formatted_cell(cell, 5, Alignment::Left)),
+                5
+            );
+        }
     }
 }
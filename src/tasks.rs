@@ -1,13 +1,24 @@
 mod add;
+mod assign_uuids;
 mod complete;
 mod delete;
 mod get_last_id;
 mod get_task_by_id;
+mod get_taskwarrior_data;
 mod list;
+mod resolve_task_ref;
+mod search;
+mod take_scope_number;
+mod task_row;
 
-pub use add::add_task;
+pub use add::{add_task, import_tasks, ImportedTasks};
+pub use assign_uuids::assign_uuids;
 pub use complete::complete_task;
 pub use delete::delete_task;
 pub use get_last_id::get_last_id;
 pub use get_task_by_id::get_task_by_id;
-pub use list::list_tasks;
+pub use get_taskwarrior_data::get_taskwarrior_data;
+pub use list::{list_tasks, ListQuery};
+pub use resolve_task_ref::resolve_task_ref;
+pub use search::{search_tasks, search_terms};
+pub use take_scope_number::take_scope_number;
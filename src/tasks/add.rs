@@ -1,30 +1,248 @@
 use anyhow::Context;
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 
 use crate::{
-    domain::{NewTask, Task},
+    domain::{NewTask, Task, TaskKey},
     generator::Generator,
+    scopes,
 };
 
-pub async fn add_task(
+use super::{get_last_id, get_task_by_id, take_scope_number};
+
+pub async fn add_task(pool: &SqlitePool, input: NewTask) -> anyhow::Result<Task> {
+    let mut transaction = pool.begin().await?;
+    let (task, scope_number) = insert_task(&mut transaction, input).await?;
+    transaction.commit().await.context("Failed to save task")?;
+    with_key(pool, task, scope_number).await
+}
+
+/// Tasks saved by an import
+#[derive(Debug, Default)]
+pub struct ImportedTasks {
+    pub added: Vec<Task>,
+    /// Tasks imported before, found by their uuid
+    pub updated: Vec<Task>,
+}
+
+/// Saves several tasks at once, none of them being saved when one fails.
+///
+/// Tasks with the uuid or calendar UID of an existing task update it instead of being added
+/// again.
+pub async fn import_tasks(
     pool: &SqlitePool,
-    generator: &Generator,
+    inputs: Vec<NewTask>,
+) -> anyhow::Result<ImportedTasks> {
+    let mut transaction = pool.begin().await?;
+    let mut inserted = vec![];
+    let mut updated_ids = vec![];
+    for input in inputs {
+        match find_imported_task(&mut transaction, &input).await? {
+            Some(id) => {
+                update_task(&mut transaction, id, input).await?;
+                updated_ids.push(id);
+            }
+            None => inserted.push(insert_task(&mut transaction, input).await?),
+        }
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to save imported tasks")?;
+    let mut imported = ImportedTasks::default();
+    for (task, scope_number) in inserted {
+        imported
+            .added
+            .push(with_key(pool, task, scope_number).await?);
+    }
+    for id in updated_ids {
+        imported.updated.extend(get_task_by_id(pool, id).await?);
+    }
+    Ok(imported)
+}
+
+/// Id of the task with the uuid or the calendar UID of an imported one, saved when it was
+/// imported or exported before
+async fn find_imported_task(
+    connection: &mut SqliteConnection,
+    input: &NewTask,
+) -> anyhow::Result<Option<u32>> {
+    let id = sqlx::query_scalar!(
+        r#"SELECT id as "id: u32" FROM tasks WHERE uuid = $1 OR calendar_uid = $2"#,
+        input.uuid,
+        input.calendar_uid
+    )
+    .fetch_optional(&mut *connection)
+    .await?;
+    Ok(id)
+}
+
+/// Replaces the fields of a task with the imported ones, keeping its creation time and the
+/// identifiers and Taskwarrior fields the import does not have
+async fn update_task(
+    connection: &mut SqliteConnection,
+    id: u32,
     input: NewTask,
-) -> anyhow::Result<Task> {
-    let task = Task::new(input, generator);
+) -> anyhow::Result<()> {
+    let current = sqlx::query!(
+        r#"SELECT scope, scope_number as "scope_number: u32" FROM tasks WHERE id = $1"#,
+        id
+    )
+    .fetch_one(&mut *connection)
+    .await?;
+    let scope_number = match input.scope.as_ref() {
+        Some(scope) if current.scope.as_deref() == Some(scope.as_ref()) => current.scope_number,
+        Some(scope) => Some(take_scope_number(&mut *connection, scope).await?),
+        None => None,
+    };
     sqlx::query!(
         r#"
-            INSERT INTO tasks (id, description, completed_at, created_at, scope)
-            VALUES ($1, $2, $3, $4, $5)
+            UPDATE tasks
+            SET description = $2, completed_at = $3, scope = $4, scope_number = $5,
+                due_at = $6, uuid = COALESCE($7, uuid),
+                taskwarrior_fields = COALESCE($8, taskwarrior_fields),
+                calendar_uid = COALESCE($9, calendar_uid)
+            WHERE id = $1
+        "#,
+        id,
+        input.description,
+        input.completed_at,
+        input.scope,
+        scope_number,
+        input.due_at,
+        input.uuid,
+        input.taskwarrior_fields,
+        input.calendar_uid
+    )
+    .execute(&mut *connection)
+    .await
+    .context("Failed to update task")?;
+    Ok(())
+}
+
+/// Inserts a task, returning it with its number within its scope.
+///
+/// Its id follows the last one saved, which other processes may have added since this one
+/// started.
+async fn insert_task(
+    connection: &mut SqliteConnection,
+    input: NewTask,
+) -> anyhow::Result<(Task, Option<u32>)> {
+    let (uuid, taskwarrior_fields, calendar_uid) = (
+        input.uuid.clone(),
+        input.taskwarrior_fields.clone(),
+        input.calendar_uid.clone(),
+    );
+    let next_id = get_last_id(&mut *connection)
+        .await?
+        .map_or(1, |previous_id| previous_id + 1);
+    let task = Task::new(input, &Generator::from(next_id));
+    let scope_number = match task.scope.as_ref() {
+        Some(scope) => Some(take_scope_number(&mut *connection, scope).await?),
+        None => None,
+    };
+    sqlx::query!(
+        r#"
+            INSERT INTO tasks (
+                id, description, completed_at, created_at, scope, scope_number, due_at,
+                uuid, taskwarrior_fields, calendar_uid
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         "#,
         task.id,
         task.description,
         task.completed_at,
         task.created_at,
-        task.scope
+        task.scope,
+        scope_number,
+        task.due_at,
+        uuid,
+        taskwarrior_fields,
+        calendar_uid
     )
-    .execute(pool)
+    .execute(&mut *connection)
     .await
     .context("Failed to save task")?;
+    Ok((task, scope_number))
+}
+
+async fn with_key(
+    pool: &SqlitePool,
+    mut task: Task,
+    scope_number: Option<u32>,
+) -> anyhow::Result<Task> {
+    if let Some(scope) = task.scope.as_ref() {
+        let prefix = scopes::get_key(pool, scope).await?;
+        task.key = TaskKey::from_parts(prefix, scope_number);
+    }
     Ok(task)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        domain::{NewTask, Scope},
+        scopes,
+        startup::test_pool,
+        tasks::{delete_task, get_taskwarrior_data},
+    };
+
+    use super::{add_task, import_tasks};
+
+    fn new_task(scope: &str) -> NewTask {
+        NewTask {
+            description: "Task".to_string(),
+            scope: Some(Scope::new(scope.to_string())),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn keys_of_deleted_tasks_are_not_reused() {
+        let pool = test_pool().await;
+        let web = Scope::new("web".to_string());
+        scopes::set_key(&pool, &web, Some("WEB".to_string()))
+            .await
+            .unwrap();
+        let mut keys = vec![];
+        for scope in ["web", "web", "ops", "web"] {
+            let task = add_task(&pool, new_task(scope)).await.unwrap();
+            keys.push(task.display_id());
+        }
+        assert_eq!(keys, ["WEB-1", "WEB-2", "3", "WEB-3"]);
+        delete_task(&pool, 4).await.unwrap();
+        let task = add_task(&pool, new_task("web")).await.unwrap();
+        assert_eq!(task.display_id(), "WEB-4");
+    }
+    #[tokio::test]
+    async fn ids_follow_tasks_saved_by_other_processes() {
+        let pool = test_pool().await;
+        add_task(&pool, new_task("web")).await.unwrap();
+        sqlx::query("INSERT INTO tasks (id, description, created_at) VALUES (5, 'Other', 0)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let task = add_task(&pool, new_task("web")).await.unwrap();
+        assert_eq!(task.id.to_string(), "6");
+    }
+    #[tokio::test]
+    async fn taskwarrior_fields_are_replaced_by_taskwarrior_imports_only() {
+        let pool = test_pool().await;
+        let import = |fields: Option<&str>| NewTask {
+            description: "Deploy".to_string(),
+            uuid: Some("5f2d7c9e-1b2a-4c3d-9e8f-0a1b2c3d4e5f".to_string()),
+            taskwarrior_fields: fields.map(str::to_string),
+            ..Default::default()
+        };
+        let saved_fields = |pool| async move {
+            let data = get_taskwarrior_data(pool).await.unwrap();
+            data.get(&1).and_then(|data| data.fields.clone())
+        };
+        import_tasks(&pool, vec![import(Some(r#"{"tags":["ops"]}"#))])
+            .await
+            .unwrap();
+        import_tasks(&pool, vec![import(None)]).await.unwrap();
+        assert_eq!(saved_fields(&pool).await.unwrap(), r#"{"tags":["ops"]}"#);
+        import_tasks(&pool, vec![import(Some("{}"))]).await.unwrap();
+        assert_eq!(saved_fields(&pool).await.unwrap(), "{}");
+    }
+}
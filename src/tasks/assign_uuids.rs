@@ -0,0 +1,26 @@
+use anyhow::Context;
+use sqlx::SqlitePool;
+
+use crate::taskwarrior::new_uuid;
+
+/// Gives a random uuid to the tasks that have none before they are exported, so that they are
+/// recognized when the file is imported again, in this database or another one. Exporters
+/// only make up a uuid of their own for tasks that skipped this step.
+pub async fn assign_uuids(pool: &SqlitePool, ids: &[u32]) -> anyhow::Result<()> {
+    let mut transaction = pool.begin().await?;
+    for id in ids {
+        let uuid = new_uuid();
+        sqlx::query!(
+            r#"UPDATE tasks SET uuid = $2 WHERE id = $1 AND uuid IS NULL"#,
+            id,
+            uuid
+        )
+        .execute(&mut *transaction)
+        .await?;
+    }
+    transaction
+        .commit()
+        .await
+        .context("Failed to save task uuids")?;
+    Ok(())
+}
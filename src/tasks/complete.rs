@@ -1,12 +1,15 @@
 use anyhow::Ok;
 use sqlx::SqlitePool;
 
+use crate::domain::Task;
+
 use super::get_task_by_id;
 
-pub async fn complete_task(pool: &SqlitePool, task_id: u32) -> anyhow::Result<bool> {
+/// Toggles task completion, returning the updated task when found
+pub async fn complete_task(pool: &SqlitePool, task_id: u32) -> anyhow::Result<Option<Task>> {
     let task = get_task_by_id(pool, task_id).await?;
     if task.is_none() {
-        return Ok(false);
+        return Ok(None);
     }
 
     let mut task = task.unwrap();
@@ -26,5 +29,5 @@ pub async fn complete_task(pool: &SqlitePool, task_id: u32) -> anyhow::Result<bo
     .rows_affected()
     .try_into()
     .unwrap();
-    Ok(rows_affected > 0)
+    Ok(Some(task).filter(|_| rows_affected > 0))
 }
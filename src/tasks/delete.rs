@@ -1,6 +1,12 @@
 use sqlx::SqlitePool;
 
-pub async fn delete_task(pool: &SqlitePool, task_id: u32) -> anyhow::Result<bool> {
+use crate::domain::Task;
+
+use super::get_task_by_id;
+
+/// Deletes a task, returning it when found
+pub async fn delete_task(pool: &SqlitePool, task_id: u32) -> anyhow::Result<Option<Task>> {
+    let task = get_task_by_id(pool, task_id).await?;
     let rows_affected = sqlx::query!(
         r#"
         DELETE FROM tasks
@@ -11,5 +17,5 @@ pub async fn delete_task(pool: &SqlitePool, task_id: u32) -> anyhow::Result<bool
     .execute(pool)
     .await?
     .rows_affected();
-    Ok(rows_affected > 0)
+    Ok(task.filter(|_| rows_affected > 0))
 }
@@ -1,9 +1,9 @@
 use anyhow::Ok;
-use sqlx::SqlitePool;
+use sqlx::SqliteExecutor;
 
-pub async fn get_last_id(pool: &SqlitePool) -> anyhow::Result<Option<u32>> {
+pub async fn get_last_id(executor: impl SqliteExecutor<'_>) -> anyhow::Result<Option<u32>> {
     let last_id = sqlx::query!(r#"SELECT id as "id: u32" FROM TASKS ORDER BY id DESC"#)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?
         .map(|r| r.id);
     Ok(last_id)
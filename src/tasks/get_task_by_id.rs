@@ -1,17 +1,31 @@
 use sqlx::SqlitePool;
 
-use crate::domain::Task;
+use crate::domain::{Task, TaskKey};
 
 pub async fn get_task_by_id(pool: &SqlitePool, task_id: u32) -> anyhow::Result<Option<Task>> {
     let row = sqlx::query!(
         r#"
-            SELECT id as "id: u32", description, completed_at, created_at, scope
-            FROM TASKS
-            WHERE id = $1
+            SELECT tasks.id as "id: u32", description, completed_at, created_at, scope,
+                scope_number as "scope_number: u32", scopes.key as scope_key,
+                due_at
+            FROM tasks
+            LEFT JOIN scopes ON scopes.name = tasks.scope
+            WHERE tasks.id = $1
         "#,
         task_id
     )
     .fetch_optional(pool)
     .await?;
-    Ok(row.and_then(|r| Task::from(r.id, r.description, r.completed_at, r.created_at, r.scope)))
+    Ok(row.and_then(|r| {
+        let key = TaskKey::from_parts(r.scope_key, r.scope_number);
+        Task::from(
+            r.id,
+            r.description,
+            r.completed_at,
+            r.created_at,
+            r.scope,
+            key,
+            r.due_at,
+        )
+    }))
 }
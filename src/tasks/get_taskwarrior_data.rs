@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use sqlx::SqlitePool;
+
+use crate::taskwarrior::TaskwarriorData;
+
+/// Taskwarrior data of the tasks imported or exported before, by task id
+pub async fn get_taskwarrior_data(
+    pool: &SqlitePool,
+) -> anyhow::Result<HashMap<u32, TaskwarriorData>> {
+    let rows = sqlx::query!(
+        r#"
+            SELECT id as "id: u32", uuid, taskwarrior_fields, calendar_uid
+            FROM tasks
+            WHERE uuid IS NOT NULL OR taskwarrior_fields IS NOT NULL
+                OR calendar_uid IS NOT NULL
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let data = TaskwarriorData {
+                uuid: row.uuid,
+                fields: row.taskwarrior_fields,
+                calendar_uid: row.calendar_uid,
+            };
+            (row.id, data)
+        })
+        .collect())
+}
@@ -1,27 +1,114 @@
 use anyhow::{anyhow, Context};
-use sqlx::SqlitePool;
+use chrono::{DateTime, Local, Utc};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
 
-use crate::domain::{Scope, Task};
+use crate::{
+    domain::{Scope, SortField, SortKey, Task, TaskStatus},
+    filter::{push_filter, FilterExpr},
+};
+
+use super::task_row::{TaskRow, TASK_COLUMNS};
+
+#[derive(Debug, Default)]
+pub struct ListQuery {
+    pub scope: Option<Scope>,
+    pub filter: Option<FilterExpr>,
+    pub status: Option<TaskStatus>,
+    pub created_since: Option<DateTime<Local>>,
+    pub created_before: Option<DateTime<Local>>,
+    pub completed_since: Option<DateTime<Local>>,
+    pub completed_before: Option<DateTime<Local>>,
+    /// Sorting criteria by priority, newest tasks first when empty
+    pub sort: Vec<SortKey>,
+    /// Flips the direction of every sorting criterion
+    pub reverse: bool,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+impl ListQuery {
+    /// Sorting criteria including the implicit id tie-breaker
+    fn sort_keys(&self) -> Vec<SortKey> {
+        let mut keys = self.sort.clone();
+        if !keys.iter().any(|k| k.field == SortField::Id) {
+            keys.push(SortKey::new(SortField::Id, true));
+        }
+        if self.reverse {
+            keys = keys.into_iter().map(SortKey::reversed).collect();
+        }
+        keys
+    }
+}
 
 pub async fn list_tasks(
     pool: &SqlitePool,
-    scope: Option<Scope>,
+    query: &ListQuery,
 ) -> anyhow::Result<Vec<Result<Task, anyhow::Error>>> {
-    let tasks = sqlx::query!(
-        r#"
-            SELECT id as "id: u32", description, completed_at, created_at, scope
-            FROM tasks
-            WHERE (scope = $1) OR ($1 is null)
-            ORDER BY id DESC
-        "#,
-        scope
-    )
-    .fetch_all(pool)
-    .await
-    .context("Failed fetching tasks")?
-    .into_iter()
-    .map(|r| Task::from(r.id, r.description, r.completed_at, r.created_at, r.scope))
-    .map(|t| t.ok_or(anyhow!("Failed to parse task")))
-    .collect();
+    let mut builder = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT {} FROM tasks LEFT JOIN scopes ON scopes.name = tasks.scope WHERE 1 = 1",
+        TASK_COLUMNS
+    ));
+    if let Some(scope) = query.scope.as_ref() {
+        builder.push(" AND tasks.scope = ");
+        builder.push_bind(scope.as_ref().to_string());
+    }
+    match query.status {
+        Some(TaskStatus::Open) => builder.push(" AND tasks.completed_at IS NULL"),
+        Some(TaskStatus::Done) => builder.push(" AND tasks.completed_at IS NOT NULL"),
+        None => &mut builder,
+    };
+    let date_bounds = [
+        ("tasks.created_at", ">=", query.created_since),
+        ("tasks.created_at", "<", query.created_before),
+        ("tasks.completed_at", ">=", query.completed_since),
+        ("tasks.completed_at", "<", query.completed_before),
+    ];
+    for (column, operator, bound) in date_bounds {
+        if let Some(bound) = bound {
+            builder.push(format!(" AND datetime({}) {} datetime(", column, operator));
+            builder.push_bind(bound.with_timezone(&Utc));
+            builder.push(")");
+        }
+    }
+    if let Some(filter) = query.filter.as_ref() {
+        builder.push(" AND (");
+        push_filter(&mut builder, filter, Local::now())?;
+        builder.push(")");
+    }
+    builder.push(" ORDER BY ");
+    for (i, key) in query.sort_keys().iter().enumerate() {
+        if i > 0 {
+            builder.push(", ");
+        }
+        builder.push(sort_expression(key));
+    }
+    if query.limit.is_some() || query.offset.is_some() {
+        builder.push(" LIMIT ");
+        builder.push_bind(query.limit.map_or(-1, i64::from));
+        builder.push(" OFFSET ");
+        builder.push_bind(query.offset.unwrap_or(0));
+    }
+
+    let tasks = builder
+        .build_query_as::<TaskRow>()
+        .fetch_all(pool)
+        .await
+        .context("Failed fetching tasks")?
+        .into_iter()
+        .map(|r| r.into_task())
+        .map(|t| t.ok_or(anyhow!("Failed to parse task")))
+        .collect();
     Ok(tasks)
 }
+
+fn sort_expression(key: &SortKey) -> String {
+    let column = match key.field {
+        SortField::Id => "tasks.id",
+        SortField::Description => "lower(tasks.description)",
+        SortField::Scope => "tasks.scope",
+        SortField::Created => "datetime(tasks.created_at)",
+        SortField::Completed => "datetime(tasks.completed_at)",
+    };
+    let direction = if key.descending { "DESC" } else { "ASC" };
+    format!("{} {} NULLS LAST", column, direction)
+}
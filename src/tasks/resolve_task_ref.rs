@@ -0,0 +1,69 @@
+use sqlx::SqlitePool;
+
+use crate::domain::TaskRef;
+
+/// Finds the global id of the task a reference points to
+pub async fn resolve_task_ref(
+    pool: &SqlitePool,
+    task_ref: &TaskRef,
+) -> anyhow::Result<Option<u32>> {
+    let key = match task_ref {
+        TaskRef::Id(id) => return Ok(Some(*id)),
+        TaskRef::Key(key) => key,
+    };
+    let id = sqlx::query!(
+        r#"
+            SELECT tasks.id as "id: u32"
+            FROM tasks
+            JOIN scopes ON scopes.name = tasks.scope
+            WHERE scopes.key = $1 AND tasks.scope_number = $2
+        "#,
+        key.prefix,
+        key.number
+    )
+    .fetch_optional(pool)
+    .await?
+    .map(|r| r.id);
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        domain::{NewTask, Scope, TaskRef},
+        scopes,
+        startup::test_pool,
+        tasks::add_task,
+    };
+
+    use super::resolve_task_ref;
+
+    #[tokio::test]
+    async fn keys_resolve_to_the_task_of_their_scope() {
+        let pool = test_pool().await;
+        for scope in ["ops", "web", "ops"] {
+            let input = NewTask {
+                description: "Task".to_string(),
+                scope: Some(Scope::new(scope.to_string())),
+                ..Default::default()
+            };
+            add_task(&pool, input).await.unwrap();
+        }
+        let ops = Scope::new("ops".to_string());
+        scopes::set_key(&pool, &ops, Some("OPS".to_string()))
+            .await
+            .unwrap();
+        let cases = [
+            ("OPS-2", Some(3)),
+            ("ops-1", Some(1)),
+            ("OPS-3", None),
+            ("WEB-1", None),
+            ("7", Some(7)),
+        ];
+        for (value, expected) in cases {
+            let task_ref: TaskRef = value.parse().unwrap();
+            let id = resolve_task_ref(&pool, &task_ref).await.unwrap();
+            assert_eq!(id, expected, "{}", value);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::{anyhow, Context};
+use sqlx::SqlitePool;
+
+use crate::domain::{Scope, Task};
+
+use super::task_row::{TaskRow, TASK_COLUMNS};
+
+/// Full-text search over task descriptions, best matches first.
+///
+/// The query uses SQLite FTS5 syntax: `"exact phrase"`, `prefix*`, `AND`, `OR` and `NOT`.
+pub async fn search_tasks(
+    pool: &SqlitePool,
+    query: &str,
+    scope: Option<Scope>,
+) -> anyhow::Result<Vec<Result<Task, anyhow::Error>>> {
+    let sql = format!(
+        r#"
+            SELECT {}
+            FROM tasks_fts
+            JOIN tasks ON tasks.id = tasks_fts.rowid
+            LEFT JOIN scopes ON scopes.name = tasks.scope
+            WHERE tasks_fts MATCH $1 AND ((tasks.scope = $2) OR ($2 is null))
+            ORDER BY tasks_fts.rank
+        "#,
+        TASK_COLUMNS
+    );
+    let tasks = sqlx::query_as::<_, TaskRow>(&sql)
+        .bind(query)
+        .bind(scope)
+        .fetch_all(pool)
+        .await
+        .with_context(|| format!("Failed searching tasks for '{}'", query))?
+        .into_iter()
+        .map(|r| r.into_task())
+        .map(|t| t.ok_or(anyhow!("Failed to parse task")))
+        .collect();
+    Ok(tasks)
+}
+
+/// Terms of a search query worth highlighting in results, prefix terms ending with `*`
+pub fn search_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| c.is_whitespace() || matches!(c, '"' | '(' | ')' | '^' | '+'))
+        .map(|term| term.rsplit(':').next().unwrap_or(term))
+        .filter(|term| !term.is_empty() && !matches!(*term, "AND" | "OR" | "NOT" | "NEAR"))
+        .map(|term| term.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::search_terms;
+
+    #[test]
+    fn operators_and_syntax_are_not_terms() {
+        let terms = search_terms(r#""deploy api" OR dep* NOT description:Staging"#);
+        assert_eq!(vec!["deploy", "api", "dep*", "staging"], terms);
+    }
+}
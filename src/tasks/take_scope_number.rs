@@ -0,0 +1,23 @@
+use sqlx::SqliteExecutor;
+
+use crate::domain::Scope;
+
+/// Takes the next number of a scope, which is never handed out again even once its task is
+/// deleted, so keys keep pointing to a single task
+pub async fn take_scope_number(
+    executor: impl SqliteExecutor<'_>,
+    scope: &Scope,
+) -> anyhow::Result<u32> {
+    let number = sqlx::query_scalar!(
+        r#"
+            INSERT INTO scopes (name, next_number)
+            VALUES ($1, 2)
+            ON CONFLICT (name) DO UPDATE SET next_number = next_number + 1
+            RETURNING next_number - 1 as "number!: u32"
+        "#,
+        scope
+    )
+    .fetch_one(executor)
+    .await?;
+    Ok(number)
+}
@@ -0,0 +1,36 @@
+use sqlx::types::chrono::NaiveDateTime;
+
+use crate::domain::{Task, TaskKey};
+
+/// Columns selected by dynamic task queries
+pub const TASK_COLUMNS: &str = r#"
+    tasks.id, tasks.description, tasks.completed_at, tasks.created_at, tasks.scope,
+    tasks.scope_number, scopes.key as scope_key, tasks.due_at
+"#;
+
+#[derive(sqlx::FromRow)]
+pub struct TaskRow {
+    id: u32,
+    description: String,
+    completed_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+    scope: Option<String>,
+    scope_number: Option<u32>,
+    scope_key: Option<String>,
+    due_at: Option<NaiveDateTime>,
+}
+
+impl TaskRow {
+    pub fn into_task(self) -> Option<Task> {
+        let key = TaskKey::from_parts(self.scope_key, self.scope_number);
+        Task::from(
+            self.id,
+            self.description,
+            self.completed_at,
+            self.created_at,
+            self.scope,
+            key,
+            self.due_at,
+        )
+    }
+}
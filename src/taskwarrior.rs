@@ -0,0 +1,230 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    dates::parse_datetime,
+    domain::{NewTask, Scope, Task},
+};
+
+/// Fields computed by Taskwarrior, which are not kept on import
+const COMPUTED_FIELDS: [&str; 3] = ["id", "urgency", "modified"];
+
+/// Dates as written by `task export`, in UTC
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Taskwarrior identity and fields of a task, saved when it was imported or exported
+#[derive(Debug, Default, Clone)]
+pub struct TaskwarriorData {
+    pub uuid: Option<String>,
+    /// UID of the VTODO the task was imported from, when it is not a uuid
+    pub calendar_uid: Option<String>,
+    /// JSON object of the fields without an equivalent, such as tags, priority or annotations
+    pub fields: Option<String>,
+}
+
+/// Task in the JSON format of `task export` and `task import`
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TaskwarriorTask {
+    uuid: String,
+    description: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(flatten)]
+    fields: Map<String, Value>,
+}
+
+impl TryFrom<TaskwarriorTask> for NewTask {
+    type Error = String;
+
+    fn try_from(task: TaskwarriorTask) -> Result<Self, Self::Error> {
+        if task.description.trim().is_empty() {
+            return Err("missing description".to_string());
+        }
+        let completed_at = match task.status.as_str() {
+            "pending" | "waiting" => None,
+            "completed" => Some(parse_date(task.end.as_deref())?.unwrap_or_else(Local::now)),
+            "deleted" => return Err("deleted in Taskwarrior".to_string()),
+            "recurring" => return Err("recurrence template".to_string()),
+            status => return Err(format!("unknown status '{}'", status)),
+        };
+        let mut fields = task.fields;
+        for field in COMPUTED_FIELDS {
+            fields.remove(field);
+        }
+        Ok(NewTask {
+            description: task.description,
+            scope: task.project.map(Scope::new),
+            due_at: parse_date(task.due.as_deref())?,
+            created_at: parse_date(task.entry.as_deref())?,
+            completed_at,
+            uuid: Some(task.uuid),
+            // Empty fields too, replacing the ones saved when the task was imported before
+            taskwarrior_fields: Some(Value::Object(fields).to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+impl TaskwarriorTask {
+    /// Task as exported, with the Taskwarrior fields saved on import
+    pub fn new(task: &Task, data: Option<&TaskwarriorData>) -> Self {
+        let fields = data
+            .and_then(|data| data.fields.as_deref())
+            .and_then(|fields| serde_json::from_str(fields).ok())
+            .unwrap_or_default();
+        let status = match task.completed_at {
+            Some(_) => "completed",
+            None => "pending",
+        };
+        Self {
+            uuid: data
+                .and_then(|data| data.uuid.clone())
+                .unwrap_or_else(new_uuid),
+            description: task.description.clone(),
+            status: status.to_string(),
+            entry: Some(format_date(task.created_at)),
+            end: task.completed_at.map(format_date),
+            due: task.due_at.map(format_date),
+            project: task.scope.as_ref().map(|scope| scope.to_string()),
+            fields,
+        }
+    }
+}
+
+/// Writes tasks as the JSON array read by `task import`
+pub fn write_taskwarrior<W: Write>(
+    writer: &mut W,
+    tasks: &[Task],
+    data: &HashMap<u32, TaskwarriorData>,
+) -> io::Result<()> {
+    let tasks: Vec<TaskwarriorTask> = tasks
+        .iter()
+        .map(|task| TaskwarriorTask::new(task, data.get(&task.id.clone().into())))
+        .collect();
+    serde_json::to_writer_pretty(&mut *writer, &tasks)?;
+    writeln!(writer)
+}
+
+/// Whether a value is a uuid as accepted by `task import`
+pub fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+/// Random version 4 uuid, such as `5f2d7c9e-1b2a-4c3d-9e8f-0a1b2c3d4e5f`
+pub fn new_uuid() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("the system provides random bytes");
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+fn format_date(date: DateTime<Local>) -> String {
+    date.naive_utc().format(DATE_FORMAT).to_string()
+}
+
+fn parse_date(value: Option<&str>) -> Result<Option<DateTime<Local>>, String> {
+    value
+        .map(|value| {
+            NaiveDateTime::parse_from_str(value, DATE_FORMAT)
+                .map(|date| date.and_utc().with_timezone(&Local))
+                .ok()
+                .or_else(|| parse_datetime(value))
+                .ok_or(format!("invalid date '{}'", value))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{Local, TimeZone, Utc};
+    use serde_json::json;
+
+    use crate::domain::{NewTask, Scope, Task};
+
+    use super::{is_uuid, new_uuid, TaskwarriorData, TaskwarriorTask};
+
+    fn parse(value: serde_json::Value) -> Result<NewTask, String> {
+        NewTask::try_from(serde_json::from_value::<TaskwarriorTask>(value).unwrap())
+    }
+
+    #[test]
+    fn taskwarrior_tasks_keep_their_uuid_and_extra_fields() {
+        let task = parse(json!({
+            "id": 0,
+            "uuid": "5f2d7c9e-1b2a-4c3d-9e8f-0a1b2c3d4e5f",
+            "description": "Deploy the API",
+            "status": "completed",
+            "entry": "20261012T090000Z",
+            "end": "20261019T090000Z",
+            "project": "Ops",
+            "tags": ["infra"],
+            "priority": "H",
+            "urgency": 4.9
+        }))
+        .unwrap();
+        assert_eq!(task.scope, Some(Scope::new("ops".to_string())));
+        assert_eq!(
+            task.created_at.unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 12, 9, 0, 0).unwrap()
+        );
+        assert!(task.completed_at.is_some());
+        assert_eq!(task.uuid.unwrap(), "5f2d7c9e-1b2a-4c3d-9e8f-0a1b2c3d4e5f");
+        let fields: serde_json::Value =
+            serde_json::from_str(&task.taskwarrior_fields.unwrap()).unwrap();
+        assert_eq!(fields, json!({"tags": ["infra"], "priority": "H"}));
+        let deleted = json!({"uuid": "x", "description": "Old", "status": "deleted"});
+        assert_eq!(parse(deleted).unwrap_err(), "deleted in Taskwarrior");
+    }
+
+    #[test]
+    fn exported_tasks_keep_their_saved_uuid() {
+        let task = Task::test(42, "Plan retro")
+            .set_created_at(Local.with_ymd_and_hms(2026, 10, 12, 9, 0, 0).unwrap());
+        let exported = serde_json::to_value(TaskwarriorTask::new(&task, None)).unwrap();
+        assert_eq!(exported["status"], "pending");
+        assert!(exported.get("end").is_none());
+        let uuid = new_uuid();
+        assert!(is_uuid(&uuid) && !is_uuid("abc@google.com"));
+        assert_eq!(&uuid[14..15], "4");
+        assert_ne!(uuid, new_uuid());
+        let data = HashMap::from([(
+            42,
+            TaskwarriorData {
+                uuid: Some("5f2d7c9e".to_string()),
+                fields: Some(r#"{"tags":["team"]}"#.to_string()),
+                ..Default::default()
+            },
+        )]);
+        let exported = serde_json::to_value(TaskwarriorTask::new(&task, data.get(&42))).unwrap();
+        assert_eq!(exported["uuid"], "5f2d7c9e");
+        assert_eq!(exported["tags"][0], "team");
+    }
+}
@@ -0,0 +1,422 @@
+use std::fmt::{Alignment, Display};
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, Local,
+};
+
+use crate::{
+    domain::Task,
+    tabular::{pad_to_width, take_width},
+};
+
+/// Output template such as `{id:>4} {?scope}[{scope}] {/scope}{description}`.
+///
+/// - `{field}` inserts a field, `{field:<10}` pads it (`<`, `^` or `>` alignment) and
+///   `{field:<10.10}` also truncates it to 10 columns
+/// - `{created_at:%Y-%m-%d}` formats a date field
+/// - `{?field}...{/field}` renders its content only when the field is not empty and
+///   `{!field}...{/field}` only when it is
+/// - `{{` and `}}` insert braces, `\t`, `\n` and `\\` insert tabs, new lines and backslashes
+#[derive(Debug, PartialEq)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Text(String),
+    Field(TemplateField, Spec),
+    Section {
+        field: TemplateField,
+        inverted: bool,
+        nodes: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemplateField {
+    Id,
+    Key,
+    Ref,
+    Description,
+    Scope,
+    Status,
+    CreatedAt,
+    CompletedAt,
+    DueAt,
+}
+
+const FIELDS: [(TemplateField, &str); 9] = [
+    (TemplateField::Id, "id"),
+    (TemplateField::Key, "key"),
+    (TemplateField::Ref, "ref"),
+    (TemplateField::Description, "description"),
+    (TemplateField::Scope, "scope"),
+    (TemplateField::Status, "status"),
+    (TemplateField::CreatedAt, "created_at"),
+    (TemplateField::CompletedAt, "completed_at"),
+    (TemplateField::DueAt, "due_at"),
+];
+
+/// Widest padding a field may ask for
+const MAX_WIDTH: usize = 1000;
+
+#[derive(Debug, PartialEq, Default)]
+enum Spec {
+    #[default]
+    Plain,
+    Date(String),
+    Padded {
+        alignment: char,
+        width: usize,
+        max_width: Option<usize>,
+    },
+}
+
+enum FieldValue {
+    Text(String),
+    Date(DateTime<Local>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TemplateError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid template at column {}: {}",
+            self.position + 1,
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+impl TemplateField {
+    fn from_name(name: &str) -> Option<Self> {
+        FIELDS.iter().find(|(_, n)| *n == name).map(|(f, _)| *f)
+    }
+
+    fn name(&self) -> &'static str {
+        FIELDS.iter().find(|(f, _)| f == self).unwrap().1
+    }
+
+    fn is_date(&self) -> bool {
+        matches!(
+            self,
+            TemplateField::CreatedAt | TemplateField::CompletedAt | TemplateField::DueAt
+        )
+    }
+
+    fn value(&self, task: &Task) -> Option<FieldValue> {
+        let text = match self {
+            TemplateField::Id => task.id.to_string(),
+            TemplateField::Key => task.key.as_ref()?.to_string(),
+            TemplateField::Ref => task.display_id(),
+            TemplateField::Description => task.description.to_string(),
+            TemplateField::Scope => task.scope.as_ref()?.to_string(),
+            TemplateField::Status => task.status().to_string(),
+            TemplateField::CreatedAt => return Some(FieldValue::Date(task.created_at)),
+            TemplateField::CompletedAt => return task.completed_at.map(FieldValue::Date),
+            TemplateField::DueAt => return task.due_at.map(FieldValue::Date),
+        };
+        Some(FieldValue::Text(text))
+    }
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        let chars: Vec<char> = template.chars().collect();
+        let mut index = 0;
+        let nodes = parse_nodes(&chars, &mut index, None)?;
+        Ok(Self { nodes })
+    }
+
+    pub fn render(&self, task: &Task) -> String {
+        let mut output = String::new();
+        render_nodes(&self.nodes, task, &mut output);
+        output
+    }
+}
+
+fn error(position: usize, message: impl Into<String>) -> TemplateError {
+    TemplateError {
+        position,
+        message: message.into(),
+    }
+}
+
+/// Parses nodes until the end of the template or the closing tag of `section`
+fn parse_nodes(
+    chars: &[char],
+    index: &mut usize,
+    section: Option<(TemplateField, usize)>,
+) -> Result<Vec<Node>, TemplateError> {
+    let mut nodes = vec![];
+    let mut text = String::new();
+    while *index < chars.len() {
+        let c = chars[*index];
+        let next = chars.get(*index + 1).copied();
+        match (c, next) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                text.push(c);
+                *index += 2;
+            }
+            ('\\', Some(escaped)) => {
+                text.push(match escaped {
+                    't' => '\t',
+                    'n' => '\n',
+                    other => other,
+                });
+                *index += 2;
+            }
+            ('}', _) => return Err(error(*index, "unmatched '}', use '}}' for a literal brace")),
+            ('{', _) => {
+                let start = *index;
+                let end = chars[start..]
+                    .iter()
+                    .position(|c| *c == '}')
+                    .map(|offset| start + offset)
+                    .ok_or_else(|| error(start, "unclosed '{'"))?;
+                let tag: String = chars[start + 1..end].iter().collect();
+                *index = end + 1;
+                if !text.is_empty() {
+                    nodes.push(Node::Text(std::mem::take(&mut text)));
+                }
+                if let Some(name) = tag.strip_prefix('/') {
+                    return match section {
+                        Some((field, _)) if field.name() == name => Ok(nodes),
+                        _ => Err(error(start, format!("unexpected closing tag '{}'", name))),
+                    };
+                }
+                nodes.push(parse_tag(chars, index, &tag, start)?);
+            }
+            _ => {
+                text.push(c);
+                *index += 1;
+            }
+        }
+    }
+    if !text.is_empty() {
+        nodes.push(Node::Text(text));
+    }
+    match section {
+        Some((field, position)) => Err(error(
+            position,
+            format!("missing closing tag {{/{}}}", field.name()),
+        )),
+        None => Ok(nodes),
+    }
+}
+
+fn parse_tag(
+    chars: &[char],
+    index: &mut usize,
+    tag: &str,
+    position: usize,
+) -> Result<Node, TemplateError> {
+    let parse_field = |name: &str| {
+        TemplateField::from_name(name).ok_or_else(|| {
+            let names: Vec<&str> = FIELDS.iter().map(|(_, n)| *n).collect();
+            error(
+                position + 1,
+                format!(
+                    "unknown field '{}', expected one of: {}",
+                    name,
+                    names.join(", ")
+                ),
+            )
+        })
+    };
+    if let Some((inverted, name)) = tag
+        .strip_prefix('?')
+        .map(|name| (false, name))
+        .or_else(|| tag.strip_prefix('!').map(|name| (true, name)))
+    {
+        let field = parse_field(name)?;
+        let nodes = parse_nodes(chars, index, Some((field, position)))?;
+        return Ok(Node::Section {
+            field,
+            inverted,
+            nodes,
+        });
+    }
+    let (name, spec) = match tag.split_once(':') {
+        Some((name, spec)) => (name, Some(spec)),
+        None => (tag, None),
+    };
+    let field = parse_field(name)?;
+    let spec_position = position + name.len() + 2;
+    let spec = match spec {
+        None => Spec::Plain,
+        Some(spec) if spec.contains('%') && !field.is_date() => {
+            return Err(error(
+                spec_position,
+                format!(
+                    "date format '{}' given to '{}', which is not a date",
+                    spec, name
+                ),
+            ))
+        }
+        Some(spec) if spec.contains('%') => {
+            if StrftimeItems::new(spec).any(|item| item == Item::Error) {
+                return Err(error(
+                    spec_position,
+                    format!("invalid date format '{}'", spec),
+                ));
+            }
+            Spec::Date(spec.to_string())
+        }
+        Some(spec) => {
+            let padding = parse_padding(spec)
+                .ok_or_else(|| error(spec_position, format!("invalid format '{}'", spec)))?;
+            if let Spec::Padded {
+                width, max_width, ..
+            } = padding
+            {
+                if width.max(max_width.unwrap_or(0)) > MAX_WIDTH {
+                    return Err(error(
+                        spec_position,
+                        format!("width in '{}' is larger than {}", spec, MAX_WIDTH),
+                    ));
+                }
+            }
+            padding
+        }
+    };
+    Ok(Node::Field(field, spec))
+}
+
+/// Parses `[<^>]width[.max_width]`
+fn parse_padding(spec: &str) -> Option<Spec> {
+    let (alignment, rest) = match spec.chars().next()? {
+        c @ ('<' | '^' | '>') => (c, &spec[1..]),
+        _ => ('<', spec),
+    };
+    let (width, max_width) = match rest.split_once('.') {
+        Some((width, max_width)) => (width, Some(max_width.parse().ok()?)),
+        None => (rest, None),
+    };
+    let width = if width.is_empty() {
+        0
+    } else {
+        width.parse().ok()?
+    };
+    Some(Spec::Padded {
+        alignment,
+        width,
+        max_width,
+    })
+}
+
+fn render_nodes(nodes: &[Node], task: &Task, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Field(field, spec) => output.push_str(&format_value(field.value(task), spec)),
+            Node::Section {
+                field,
+                inverted,
+                nodes,
+            } => {
+                if field.value(task).is_some() != *inverted {
+                    render_nodes(nodes, task, output);
+                }
+            }
+        }
+    }
+}
+
+fn format_value(value: Option<FieldValue>, spec: &Spec) -> String {
+    let text = match (value, spec) {
+        (None, _) => String::new(),
+        (Some(FieldValue::Date(date)), Spec::Date(format)) => date.format(format).to_string(),
+        (Some(FieldValue::Date(date)), _) => date.format("%Y-%m-%d %H:%M:%S").to_string(),
+        (Some(FieldValue::Text(text)), _) => text,
+    };
+    match spec {
+        Spec::Padded {
+            alignment,
+            width,
+            max_width,
+        } => {
+            let text = match max_width {
+                Some(max_width) => take_width(&text, *max_width),
+                None => &text,
+            };
+            let alignment = match alignment {
+                '>' => Alignment::Right,
+                '^' => Alignment::Center,
+                _ => Alignment::Left,
+            };
+            pad_to_width(text, *width, alignment)
+        }
+        _ => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::Task;
+
+    use super::Template;
+
+    fn task(scope: Option<&str>) -> Task {
+        Task::test(7, "Write docs").set_scope(scope)
+    }
+
+    fn render(template: &str, task: &Task) -> String {
+        Template::parse(template).unwrap().render(task)
+    }
+
+    #[test]
+    fn fields_are_padded_and_formatted() {
+        let task = task(Some("work"));
+        assert_eq!(
+            "   7|work\tWrite|2024-08-05",
+            render(
+                r"{id:>4}|{scope}\t{description:.5}|{created_at:%Y-%m-%d}",
+                &task
+            )
+        );
+        assert_eq!("{7}", render("{{{id}}}", &task));
+    }
+
+    #[test]
+    fn sections_depend_on_empty_fields() {
+        let template =
+            "{?scope}[{scope}] {/scope}{!completed_at}todo: {/completed_at}{description}";
+        assert_eq!(
+            "[work] todo: Write docs",
+            render(template, &task(Some("work")))
+        );
+        assert_eq!("todo: Write docs", render(template, &task(None)));
+    }
+
+    #[test]
+    fn invalid_templates_are_rejected() {
+        let cases = [
+            ("{owner}", 1),
+            ("{id", 0),
+            ("id}", 2),
+            ("{?scope}x", 0),
+            ("{id}{/scope}", 4),
+            ("{id:>x}", 4),
+            ("{created_at:%Q}", 12),
+            ("x {due_at:%Y %}", 10),
+            ("{scope:%Y}", 7),
+            ("{id:999999999999999999}", 4),
+            ("{description:.1001}", 13),
+        ];
+        for (template, position) in cases {
+            let error = Template::parse(template).unwrap_err();
+            assert_eq!(position, error.position, "{}", template);
+        }
+    }
+}
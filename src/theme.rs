@@ -0,0 +1,247 @@
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::{domain::Color, tabular::supports_ansi_colors};
+
+/// When to style output with ANSI escape codes
+#[derive(ValueEnum, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// Always, even when piped or with NO_COLOR set
+    Always,
+    /// Only on terminals, unless NO_COLOR is set
+    #[default]
+    Auto,
+    /// Never
+    Never,
+}
+
+impl ColorMode {
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Auto => supports_ansi_colors(),
+            ColorMode::Never => false,
+        }
+    }
+}
+
+/// Escape code turning every attribute off
+pub const RESET: &str = "\x1b[0m";
+
+/// Text attributes such as `bold bright-red`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Style {
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub color: Option<Color>,
+}
+
+impl Style {
+    pub fn color(color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..Default::default()
+        }
+    }
+
+    /// Adds the attributes of `other`, its color replacing this one
+    pub fn patch(self, other: Style) -> Self {
+        Self {
+            bold: self.bold || other.bold,
+            dim: self.dim || other.dim,
+            italic: self.italic || other.italic,
+            underline: self.underline || other.underline,
+            color: other.color.or(self.color),
+        }
+    }
+
+    pub fn paint(&self, text: &str) -> String {
+        let codes: Vec<String> = [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, code)| code.to_string())
+        .chain(self.color.map(|color| color.ansi_code().to_string()))
+        .collect();
+        if codes.is_empty() {
+            return text.to_string();
+        }
+        let start = format!("\x1b[{}m", codes.join(";"));
+        // Styled parts of the text, such as search matches, end with a reset
+        let text = text.replace(RESET, &format!("{}{}", RESET, start));
+        format!("{}{}{}", start, text, RESET)
+    }
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut style = Style::default();
+        for word in s.split_whitespace() {
+            match word.to_lowercase().as_str() {
+                "plain" | "none" => {}
+                "bold" => style.bold = true,
+                "dim" => style.dim = true,
+                "italic" => style.italic = true,
+                "underline" => style.underline = true,
+                color => style.color = Some(color.parse()?),
+            }
+        }
+        Ok(style)
+    }
+}
+
+impl<'de> Deserialize<'de> for Style {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Lines drawn around and between table cells
+#[derive(ValueEnum, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BorderStyle {
+    /// `+`, `-` and `|`
+    #[default]
+    Ascii,
+    /// Box drawing with rounded corners
+    Rounded,
+    /// Thick box drawing
+    Heavy,
+    /// Columns separated by spaces and a rule under the header
+    Minimal,
+    /// Columns separated by spaces only
+    None,
+}
+
+/// Characters of a border style, corners listed left, middle and right
+pub struct Borders {
+    pub vertical: char,
+    /// Whether the table is closed by left, right, top and bottom lines
+    pub outer: bool,
+    pub horizontal: Option<char>,
+    pub top: [char; 3],
+    pub middle: [char; 3],
+    pub bottom: [char; 3],
+}
+
+impl BorderStyle {
+    pub fn borders(&self) -> Borders {
+        match self {
+            BorderStyle::Ascii => Borders {
+                vertical: '|',
+                outer: true,
+                horizontal: Some('-'),
+                top: ['+', '+', '+'],
+                middle: ['+', '+', '+'],
+                bottom: ['+', '+', '+'],
+            },
+            BorderStyle::Rounded => Borders {
+                vertical: '│',
+                outer: true,
+                horizontal: Some('─'),
+                top: ['╭', '┬', '╮'],
+                middle: ['├', '┼', '┤'],
+                bottom: ['╰', '┴', '╯'],
+            },
+            BorderStyle::Heavy => Borders {
+                vertical: '┃',
+                outer: true,
+                horizontal: Some('━'),
+                top: ['┏', '┳', '┓'],
+                middle: ['┣', '╋', '┫'],
+                bottom: ['┗', '┻', '┛'],
+            },
+            BorderStyle::Minimal => Borders {
+                vertical: ' ',
+                outer: false,
+                horizontal: Some('─'),
+                top: [' '; 3],
+                middle: [' '; 3],
+                bottom: [' '; 3],
+            },
+            BorderStyle::None => Borders {
+                vertical: ' ',
+                outer: false,
+                horizontal: None,
+                top: [' '; 3],
+                middle: [' '; 3],
+                bottom: [' '; 3],
+            },
+        }
+    }
+}
+
+/// Table appearance, from the `[theme]` section of the configuration
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    pub border: BorderStyle,
+    pub header: Style,
+    /// Rows of completed tasks
+    pub completed: Style,
+    /// Rows of open tasks past their due date
+    pub overdue: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            border: BorderStyle::Ascii,
+            header: Style {
+                bold: true,
+                ..Default::default()
+            },
+            completed: Style {
+                dim: true,
+                ..Default::default()
+            },
+            overdue: Style::color(Color::Red),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::Color;
+
+    use super::{Style, Theme};
+
+    #[test]
+    fn styles_are_parsed_from_words() {
+        let style: Style = "bold bright-red".parse().unwrap();
+        assert_eq!(style.paint("late"), "\x1b[1;91mlate\x1b[0m");
+        assert_eq!("plain".parse::<Style>().unwrap().paint("x"), "x");
+        assert!("bold purple".parse::<Style>().is_err());
+    }
+
+    #[test]
+    fn styles_are_restored_after_nested_ones() {
+        let style: Style = "dim".parse().unwrap();
+        assert_eq!(
+            style.paint("a \x1b[1mb\x1b[0m c"),
+            "\x1b[2ma \x1b[1mb\x1b[0m\x1b[2m c\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn themes_override_defaults() {
+        let theme: Theme = toml::from_str("border = 'rounded'\noverdue = 'bold yellow'").unwrap();
+        assert_eq!(theme.header, Theme::default().header);
+        assert_eq!(theme.overdue.color, Some(Color::Yellow));
+        assert!(theme.overdue.bold);
+        assert!(toml::from_str::<Theme>("borders = 'heavy'").is_err());
+    }
+}
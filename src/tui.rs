@@ -0,0 +1,109 @@
+mod state;
+mod ui;
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyEventKind},
+    DefaultTerminal,
+};
+
+use crate::{
+    domain::NewTask,
+    filter::parse_filter,
+    scopes,
+    startup::Application,
+    tasks::{add_task, complete_task, delete_task, list_tasks, ListQuery},
+};
+
+use self::state::{Action, ScopeFilter, TuiState};
+
+/// Runs the full-screen interface until the user quits
+pub async fn run(app: &Application) -> anyhow::Result<()> {
+    let mut state = TuiState::default();
+    reload(app, &mut state).await?;
+    let mut terminal = ratatui::init();
+    let result = event_loop(app, &mut terminal, &mut state).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(
+    app: &Application,
+    terminal: &mut DefaultTerminal,
+    state: &mut TuiState,
+) -> anyhow::Result<()> {
+    loop {
+        terminal.draw(|frame| ui::draw(frame, state, &app.settings.theme))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let Some(action) = state.handle_key(key) else {
+            continue;
+        };
+        if action == Action::Quit {
+            return Ok(());
+        }
+        // Failures, such as a locked database, are shown without leaving the interface
+        let applied = apply(app, state, action).await;
+        let reloaded = reload(app, state).await;
+        state.message = match (applied, reloaded) {
+            (Ok(message), Ok(())) => message,
+            (Err(error), _) | (_, Err(error)) => Some(format!("Error: {:#}", error)),
+        };
+    }
+}
+
+/// Carries out an action, returning the message telling how it went
+async fn apply(
+    app: &Application,
+    state: &TuiState,
+    action: Action,
+) -> anyhow::Result<Option<String>> {
+    let message = match action {
+        Action::Quit => None,
+        Action::Add(description) => {
+            let input = NewTask {
+                description,
+                scope: state.scope_filter().scope(),
+                due_at: None,
+                ..Default::default()
+            };
+            let task = add_task(&app.pool, input).await?;
+            Some(format!("Added task {}", task.display_id()))
+        }
+        Action::ToggleComplete(id) => match complete_task(&app.pool, id).await? {
+            Some(task) if task.completed_at.is_some() => {
+                Some(format!("Completed task {}", task.display_id()))
+            }
+            Some(task) => Some(format!("Reopened task {}", task.display_id())),
+            None => Some(format!("Task with id {} not found", id)),
+        },
+        Action::Delete(id) => match delete_task(&app.pool, id).await? {
+            Some(task) => Some(format!("Deleted task {}", task.display_id())),
+            None => Some(format!("Task with id {} not found", id)),
+        },
+        Action::Reload => None,
+    };
+    Ok(message)
+}
+
+/// Loads the scopes and the tasks of the selected scope
+async fn reload(app: &Application, state: &mut TuiState) -> anyhow::Result<()> {
+    state.set_scopes(scopes::list(&app.pool).await?);
+    let query = match state.scope_filter() {
+        ScopeFilter::All => ListQuery::default(),
+        ScopeFilter::NoScope => ListQuery {
+            filter: Some(parse_filter("scope:none")?),
+            ..Default::default()
+        },
+        ScopeFilter::Scope(scope) => ListQuery {
+            scope: Some(scope.clone()),
+            ..Default::default()
+        },
+    };
+    let tasks = list_tasks(&app.pool, &query).await?;
+    state.set_tasks(tasks.into_iter().filter_map(|x| x.ok()).collect());
+    Ok(())
+}
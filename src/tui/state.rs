@@ -0,0 +1,365 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::domain::{Scope, Task};
+
+/// Entry of the scopes sidebar
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScopeFilter {
+    All,
+    NoScope,
+    Scope(Scope),
+}
+
+impl ScopeFilter {
+    pub fn name(&self) -> String {
+        match self {
+            ScopeFilter::All => "All".to_string(),
+            ScopeFilter::NoScope => "No scope".to_string(),
+            ScopeFilter::Scope(scope) => scope.to_string(),
+        }
+    }
+
+    /// Scope given to tasks added while the filter is selected
+    pub fn scope(&self) -> Option<Scope> {
+        match self {
+            ScopeFilter::Scope(scope) => Some(scope.clone()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Focus {
+    Tasks,
+    Scopes,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mode {
+    Normal,
+    /// Typing the description of a new task
+    Adding(String),
+    /// Typing the search, which filters tasks as it changes
+    Searching,
+    /// Waiting for the deletion of a task to be confirmed
+    ConfirmDelete(u32),
+}
+
+/// Change requested by a key press, carried out by the event loop
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Quit,
+    Add(String),
+    ToggleComplete(u32),
+    Delete(u32),
+    /// Tasks must be loaded again, the scope filter changed
+    Reload,
+}
+
+/// Everything shown by the TUI, updated by key presses
+#[derive(Debug)]
+pub struct TuiState {
+    pub tasks: Vec<Task>,
+    pub scopes: Vec<ScopeFilter>,
+    pub selected_scope: usize,
+    /// Index of the selected task among the visible ones
+    pub selected_task: usize,
+    pub search: String,
+    pub mode: Mode,
+    pub focus: Focus,
+    /// Outcome of the last action, shown in the status line
+    pub message: Option<String>,
+}
+
+impl Default for TuiState {
+    fn default() -> Self {
+        Self {
+            tasks: vec![],
+            scopes: vec![ScopeFilter::All, ScopeFilter::NoScope],
+            selected_scope: 0,
+            selected_task: 0,
+            search: String::new(),
+            mode: Mode::Normal,
+            focus: Focus::Tasks,
+            message: None,
+        }
+    }
+}
+
+impl TuiState {
+    pub fn scope_filter(&self) -> &ScopeFilter {
+        &self.scopes[self.selected_scope]
+    }
+
+    /// Replaces the sidebar scopes, keeping the selected one when it still exists
+    pub fn set_scopes(&mut self, mut scopes: Vec<Scope>) {
+        let selected = self.scope_filter().clone();
+        scopes.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+        self.scopes = [ScopeFilter::All, ScopeFilter::NoScope]
+            .into_iter()
+            .chain(scopes.into_iter().map(ScopeFilter::Scope))
+            .collect();
+        self.selected_scope = self.scopes.iter().position(|s| *s == selected).unwrap_or(0);
+    }
+
+    /// Replaces the tasks, keeping the selected task when it still exists
+    pub fn set_tasks(&mut self, tasks: Vec<Task>) {
+        let selected = self.selected_task().map(|task| task.id.clone());
+        self.tasks = tasks;
+        if let Some(index) =
+            selected.and_then(|id| self.visible_tasks().iter().position(|task| task.id == id))
+        {
+            self.selected_task = index;
+        }
+        self.clamp_selection();
+    }
+
+    /// Tasks matching the search, by description or id
+    pub fn visible_tasks(&self) -> Vec<&Task> {
+        let search = self.search.to_lowercase();
+        self.tasks
+            .iter()
+            .filter(|task| {
+                search.is_empty()
+                    || task.description.to_lowercase().contains(&search)
+                    || task.display_id().to_lowercase().contains(&search)
+            })
+            .collect()
+    }
+
+    pub fn selected_task(&self) -> Option<&Task> {
+        self.visible_tasks().get(self.selected_task).copied()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        match self.mode.clone() {
+            Mode::Normal => self.handle_normal_key(key.code),
+            Mode::Adding(description) => self.handle_adding_key(key.code, description),
+            Mode::Searching => self.handle_search_key(key.code),
+            Mode::ConfirmDelete(id) => {
+                self.mode = Mode::Normal;
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => Some(Action::Delete(id)),
+                    _ => {
+                        self.message = Some("Deletion cancelled".to_string());
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_normal_key(&mut self, code: KeyCode) -> Option<Action> {
+        self.message = None;
+        match code {
+            KeyCode::Char('q') => return Some(Action::Quit),
+            KeyCode::Esc if self.search.is_empty() => return Some(Action::Quit),
+            KeyCode::Esc => self.set_search(String::new()),
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.focus = match self.focus {
+                    Focus::Tasks => Focus::Scopes,
+                    Focus::Scopes => Focus::Tasks,
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') => self.focus = Focus::Scopes,
+            KeyCode::Right | KeyCode::Char('l') => self.focus = Focus::Tasks,
+            KeyCode::Down | KeyCode::Char('j') => return self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => return self.move_selection(-1),
+            KeyCode::Home | KeyCode::Char('g') => return self.move_selection(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => return self.move_selection(isize::MAX),
+            KeyCode::Char('a') => self.mode = Mode::Adding(String::new()),
+            KeyCode::Char('/') => {
+                self.focus = Focus::Tasks;
+                self.mode = Mode::Searching;
+            }
+            KeyCode::Char('r') => return Some(Action::Reload),
+            KeyCode::Char('x') | KeyCode::Char(' ') | KeyCode::Enter => {
+                return self
+                    .selected_task()
+                    .map(|task| Action::ToggleComplete(task.id.clone().into()))
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(task) = self.selected_task() {
+                    self.mode = Mode::ConfirmDelete(task.id.clone().into());
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_adding_key(&mut self, code: KeyCode, mut description: String) -> Option<Action> {
+        match code {
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                let description = description.trim();
+                if !description.is_empty() {
+                    return Some(Action::Add(description.to_string()));
+                }
+            }
+            KeyCode::Backspace => {
+                description.pop();
+                self.mode = Mode::Adding(description);
+            }
+            KeyCode::Char(c) => {
+                description.push(c);
+                self.mode = Mode::Adding(description);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_search_key(&mut self, code: KeyCode) -> Option<Action> {
+        match code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.set_search(String::new());
+            }
+            KeyCode::Enter | KeyCode::Down | KeyCode::Up => self.mode = Mode::Normal,
+            KeyCode::Backspace => {
+                let mut search = self.search.clone();
+                search.pop();
+                self.set_search(search);
+            }
+            KeyCode::Char(c) => self.set_search(format!("{}{}", self.search, c)),
+            _ => {}
+        }
+        None
+    }
+
+    fn set_search(&mut self, search: String) {
+        self.search = search;
+        self.selected_task = 0;
+    }
+
+    /// Moves the selection of the focused pane, saturating at both ends
+    fn move_selection(&mut self, offset: isize) -> Option<Action> {
+        match self.focus {
+            Focus::Tasks => {
+                self.selected_task = self.selected_task.saturating_add_signed(offset);
+                self.clamp_selection();
+                None
+            }
+            Focus::Scopes => {
+                let previous = self.selected_scope;
+                self.selected_scope = self
+                    .selected_scope
+                    .saturating_add_signed(offset)
+                    .min(self.scopes.len() - 1);
+                (self.selected_scope != previous).then_some(Action::Reload)
+            }
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        let visible = self.visible_tasks().len();
+        self.selected_task = self.selected_task.min(visible.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+    use crate::domain::{Scope, Task};
+
+    use super::{Action, Focus, Mode, ScopeFilter, TuiState};
+
+    fn state() -> TuiState {
+        let mut state = TuiState::default();
+        state.set_tasks(vec![
+            Task::test(3, "Deploy the API"),
+            Task::test(2, "Write docs"),
+            Task::test(1, "Review deploy script"),
+        ]);
+        state
+    }
+
+    fn press(state: &mut TuiState, keys: &str) -> Option<Action> {
+        keys.chars()
+            .map(|c| state.handle_key(KeyEvent::from(KeyCode::Char(c))))
+            .last()
+            .flatten()
+    }
+
+    fn press_code(state: &mut TuiState, code: KeyCode) -> Option<Action> {
+        state.handle_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn selection_moves_within_the_list() {
+        let mut state = state();
+        press(&mut state, "k");
+        assert_eq!(state.selected_task, 0);
+        press(&mut state, "jjjj");
+        assert_eq!(state.selected_task, 2);
+        press(&mut state, "g");
+        assert_eq!(press(&mut state, "x"), Some(Action::ToggleComplete(3)));
+        press_code(&mut state, KeyCode::End);
+        assert_eq!(press(&mut state, " "), Some(Action::ToggleComplete(1)));
+    }
+
+    #[test]
+    fn tasks_are_added_inline() {
+        let mut state = state();
+        press(&mut state, "aPlan retrox");
+        press_code(&mut state, KeyCode::Backspace);
+        assert_eq!(state.mode, Mode::Adding("Plan retro".to_string()));
+        assert_eq!(
+            press_code(&mut state, KeyCode::Enter),
+            Some(Action::Add("Plan retro".to_string()))
+        );
+        press(&mut state, "a");
+        assert_eq!(press_code(&mut state, KeyCode::Esc), None);
+        assert_eq!(state.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn deletion_requires_confirmation() {
+        let mut state = state();
+        assert_eq!(press(&mut state, "jdn"), None);
+        assert_eq!(state.mode, Mode::Normal);
+        assert_eq!(press(&mut state, "dy"), Some(Action::Delete(2)));
+    }
+
+    #[test]
+    fn search_filters_tasks_as_it_is_typed() {
+        let mut state = state();
+        press(&mut state, "/depl");
+        let visible: Vec<String> = state
+            .visible_tasks()
+            .iter()
+            .map(|task| task.id.to_string())
+            .collect();
+        assert_eq!(visible, ["3", "1"]);
+        press_code(&mut state, KeyCode::Enter);
+        assert_eq!(press(&mut state, "jx"), Some(Action::ToggleComplete(1)));
+        press_code(&mut state, KeyCode::Esc);
+        assert_eq!(state.visible_tasks().len(), 3);
+        assert_eq!(press_code(&mut state, KeyCode::Esc), Some(Action::Quit));
+    }
+
+    #[test]
+    fn scope_sidebar_changes_the_filter() {
+        let mut state = state();
+        state.set_scopes(vec![
+            Scope::new("work".to_string()),
+            Scope::new("home".to_string()),
+        ]);
+        press_code(&mut state, KeyCode::Tab);
+        assert_eq!(state.focus, Focus::Scopes);
+        assert_eq!(press(&mut state, "k"), None);
+        assert_eq!(press(&mut state, "jj"), Some(Action::Reload));
+        assert_eq!(
+            *state.scope_filter(),
+            ScopeFilter::Scope(Scope::new("home".to_string()))
+        );
+        state.set_scopes(vec![Scope::new("home".to_string())]);
+        assert_eq!(state.scope_filter().name(), "home");
+    }
+}
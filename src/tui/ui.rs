@@ -0,0 +1,162 @@
+use chrono::Local;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style as TuiStyle, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, List, ListItem, ListState, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    domain::{Color, Task},
+    theme::{Style, Theme},
+};
+
+use super::state::{Focus, Mode, TuiState};
+
+const HELP: &str = "j/k move  a add  x toggle  d delete  / search  tab scopes  q quit";
+
+pub fn draw(frame: &mut Frame, state: &TuiState, theme: &Theme) {
+    let [main, status] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+    let [sidebar, tasks] =
+        Layout::horizontal([Constraint::Length(22), Constraint::Min(20)]).areas(main);
+    draw_scopes(frame, state, sidebar);
+    draw_tasks(frame, state, theme, tasks);
+    frame.render_widget(status_line(state), status);
+}
+
+fn draw_scopes(frame: &mut Frame, state: &TuiState, area: Rect) {
+    let items: Vec<ListItem> = state
+        .scopes
+        .iter()
+        .map(|scope| ListItem::new(scope.name()))
+        .collect();
+    let list = List::new(items)
+        .block(pane("Scopes", state.focus == Focus::Scopes))
+        .highlight_style(TuiStyle::new().add_modifier(Modifier::REVERSED));
+    let mut list_state = ListState::default().with_selected(Some(state.selected_scope));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn draw_tasks(frame: &mut Frame, state: &TuiState, theme: &Theme, area: Rect) {
+    let now = Local::now();
+    let tasks = state.visible_tasks();
+    let rows: Vec<Row> = tasks
+        .iter()
+        .map(|task| {
+            let style = match task.completed_at {
+                _ if task.is_overdue(now) => theme.overdue,
+                Some(_) => theme.completed,
+                None => Style::default(),
+            };
+            Row::new([
+                Cell::from(task.completed_at.map_or("[ ]", |_| "[x]")),
+                Cell::from(task.display_id()),
+                Cell::from(task.description.clone()),
+                Cell::from(task.scope.as_ref().map_or(String::new(), |s| s.to_string())),
+                Cell::from(due_date(task)),
+            ])
+            .style(to_tui_style(style))
+        })
+        .collect();
+    let title = match state.search.is_empty() {
+        true => format!("Tasks ({})", tasks.len()),
+        false => format!("Tasks ({}) matching '{}'", tasks.len(), state.search),
+    };
+    let header =
+        Row::new(["", "ID", "Description", "Scope", "Due"]).style(to_tui_style(theme.header));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Length(8),
+            Constraint::Min(10),
+            Constraint::Length(12),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header)
+    .block(pane(&title, state.focus == Focus::Tasks))
+    .row_highlight_style(TuiStyle::new().add_modifier(Modifier::REVERSED));
+    let mut table_state = TableState::default().with_selected(Some(state.selected_task));
+    frame.render_stateful_widget(table, area, &mut table_state);
+}
+
+fn status_line(state: &TuiState) -> Line<'_> {
+    match &state.mode {
+        Mode::Adding(description) => Line::from(vec![
+            Span::from("New task: ").bold(),
+            Span::from(description.as_str()),
+            Span::from("█"),
+        ]),
+        Mode::Searching => Line::from(vec![
+            Span::from("/").bold(),
+            Span::from(state.search.as_str()),
+            Span::from("█"),
+        ]),
+        Mode::ConfirmDelete(_) => {
+            let id = state
+                .selected_task()
+                .map_or(String::new(), |task| task.display_id());
+            Line::from(format!("Delete task {}? (y/n)", id)).bold()
+        }
+        Mode::Normal => match &state.message {
+            Some(message) => Line::from(message.as_str()),
+            None => Line::from(HELP).dim(),
+        },
+    }
+}
+
+fn pane(title: &str, focused: bool) -> Block<'static> {
+    let block = Block::bordered().title(format!(" {} ", title));
+    match focused {
+        true => block.border_style(TuiStyle::new().bold()),
+        false => block.border_style(TuiStyle::new().dim()),
+    }
+}
+
+fn due_date(task: &Task) -> String {
+    task.due_at
+        .map_or(String::new(), |d| d.format("%Y-%m-%d").to_string())
+}
+
+fn to_tui_style(style: Style) -> TuiStyle {
+    let mut tui_style = TuiStyle::new();
+    if let Some(color) = style.color {
+        tui_style = tui_style.fg(to_tui_color(color));
+    }
+    for (enabled, modifier) in [
+        (style.bold, Modifier::BOLD),
+        (style.dim, Modifier::DIM),
+        (style.italic, Modifier::ITALIC),
+        (style.underline, Modifier::UNDERLINED),
+    ] {
+        if enabled {
+            tui_style = tui_style.add_modifier(modifier);
+        }
+    }
+    tui_style
+}
+
+fn to_tui_color(color: Color) -> ratatui::style::Color {
+    use ratatui::style::Color as TuiColor;
+    match color {
+        Color::Black => TuiColor::Black,
+        Color::Red => TuiColor::Red,
+        Color::Green => TuiColor::Green,
+        Color::Yellow => TuiColor::Yellow,
+        Color::Blue => TuiColor::Blue,
+        Color::Magenta => TuiColor::Magenta,
+        Color::Cyan => TuiColor::Cyan,
+        Color::White => TuiColor::Gray,
+        Color::BrightBlack => TuiColor::DarkGray,
+        Color::BrightRed => TuiColor::LightRed,
+        Color::BrightGreen => TuiColor::LightGreen,
+        Color::BrightYellow => TuiColor::LightYellow,
+        Color::BrightBlue => TuiColor::LightBlue,
+        Color::BrightMagenta => TuiColor::LightMagenta,
+        Color::BrightCyan => TuiColor::LightCyan,
+        Color::BrightWhite => TuiColor::White,
+    }
+}
@@ -0,0 +1,12 @@
+mod delete;
+mod get;
+mod list;
+mod save;
+
+pub use delete::delete;
+pub use get::get;
+pub use list::list;
+pub use save::{save, update};
+
+/// Views store one list argument per line
+const ARGUMENT_SEPARATOR: char = '\n';
@@ -0,0 +1,15 @@
+use sqlx::SqlitePool;
+
+pub async fn delete(pool: &SqlitePool, name: &str) -> anyhow::Result<bool> {
+    let rows_affected = sqlx::query!(
+        r#"
+        DELETE FROM views
+        WHERE name = $1
+    "#,
+        name
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}
@@ -0,0 +1,23 @@
+use sqlx::SqlitePool;
+
+use crate::domain::View;
+
+use super::ARGUMENT_SEPARATOR;
+
+pub async fn get(pool: &SqlitePool, name: &str) -> anyhow::Result<Option<View>> {
+    let view = sqlx::query!(r#"SELECT name, arguments FROM views WHERE name = $1"#, name)
+        .fetch_optional(pool)
+        .await?
+        .map(|r| View::new(r.name, decode_arguments(&r.arguments)));
+    Ok(view)
+}
+
+pub(super) fn decode_arguments(arguments: &str) -> Vec<String> {
+    if arguments.is_empty() {
+        return vec![];
+    }
+    arguments
+        .split(ARGUMENT_SEPARATOR)
+        .map(str::to_string)
+        .collect()
+}
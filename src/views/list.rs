@@ -0,0 +1,15 @@
+use sqlx::SqlitePool;
+
+use crate::domain::View;
+
+use super::get::decode_arguments;
+
+pub async fn list(pool: &SqlitePool) -> anyhow::Result<Vec<View>> {
+    let views = sqlx::query!(r#"SELECT name, arguments FROM views ORDER BY name"#)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|r| View::new(r.name, decode_arguments(&r.arguments)))
+        .collect();
+    Ok(views)
+}
@@ -0,0 +1,50 @@
+use anyhow::{bail, Context};
+use sqlx::SqlitePool;
+
+use crate::domain::View;
+
+use super::ARGUMENT_SEPARATOR;
+
+fn encode_arguments(view: &View) -> anyhow::Result<String> {
+    if view
+        .arguments
+        .iter()
+        .any(|a| a.contains(ARGUMENT_SEPARATOR))
+    {
+        bail!("View arguments cannot contain line breaks");
+    }
+    Ok(view.arguments.join(&ARGUMENT_SEPARATOR.to_string()))
+}
+
+pub async fn save(pool: &SqlitePool, view: &View) -> anyhow::Result<()> {
+    let arguments = encode_arguments(view)?;
+    sqlx::query!(
+        r#"
+            INSERT INTO views (name, arguments)
+            VALUES ($1, $2)
+        "#,
+        view.name,
+        arguments
+    )
+    .execute(pool)
+    .await
+    .with_context(|| format!("Failed to save view {}, does it already exist?", view.name))?;
+    Ok(())
+}
+
+pub async fn update(pool: &SqlitePool, view: &View) -> anyhow::Result<bool> {
+    let arguments = encode_arguments(view)?;
+    let rows_affected = sqlx::query!(
+        r#"
+            UPDATE views
+            SET arguments = $1
+            WHERE name = $2
+        "#,
+        arguments,
+        view.name
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+    Ok(rows_affected > 0)
+}